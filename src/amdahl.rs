@@ -0,0 +1,149 @@
+use crate::fitter::minimize_with_fallback;
+use crate::{Measurement, Model, Objective, ScalabilityModel, MIN_MEASUREMENTS};
+
+/// A model of a system which obeys Amdahl's Law: `X(N) = λN/(1 + σ(N-1))`, where σ is the fraction
+/// of work which is serial.
+///
+/// This is the Universal Scalability Law with no coherency penalty. Throughput approaches `λ/σ` as
+/// concurrency increases, but never declines. It is often the right model for CPU-bound workloads.
+///
+/// ```
+/// use usl::{AmdahlModel, Measurement};
+///
+/// let measurements: Vec<Measurement> =
+///     [(1, 100.0), (2, 182.0), (4, 308.0), (8, 471.0), (16, 640.0), (32, 780.0)]
+///         .iter()
+///         .map(|&m| m.into())
+///         .collect();
+/// let model = AmdahlModel::build(&measurements);
+/// println!("max speedup: {}", model.max_speedup());
+/// ```
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AmdahlModel {
+    /// The fraction of work which is serial, σ.
+    pub serial_fraction: f64,
+    /// The throughput of a single concurrent event, λ.
+    pub lambda: f64,
+}
+
+impl AmdahlModel {
+    /// Build a model whose parameters are generated from the given measurements.
+    #[must_use]
+    pub fn build(measurements: &[Measurement]) -> AmdahlModel {
+        assert!(
+            measurements.len() >= MIN_MEASUREMENTS,
+            "must have at least {} measurements",
+            MIN_MEASUREMENTS
+        );
+        let lambda = measurements.iter().map(|m| m.x / m.n).fold(f64::NEG_INFINITY, f64::max);
+        let mut params = [0.1, lambda];
+        if let Err(err) = minimize_with_fallback(&AmdahlFitter(measurements), &mut params) {
+            panic!("{}", err)
+        }
+        AmdahlModel { serial_fraction: params[0], lambda: params[1] }
+    }
+
+    /// Calculate the expected throughput given a number of concurrent events, `X(N)`.
+    #[must_use]
+    pub fn throughput_at_concurrency(&self, n: u32) -> f64 {
+        self.throughput_at_concurrency_f64(n.into())
+    }
+
+    /// Calculate the expected throughput given a possibly fractional average number of concurrent
+    /// events, `X(N)`.
+    #[must_use]
+    pub fn throughput_at_concurrency_f64(&self, n: f64) -> f64 {
+        self.lambda * n / (1.0 + self.serial_fraction * (n - 1.0))
+    }
+
+    /// Calculate the expected mean latency given a number of concurrent events, `R(N)`.
+    #[must_use]
+    pub fn latency_at_concurrency(&self, n: u32) -> f64 {
+        (1.0 + self.serial_fraction * (f64::from(n) - 1.0)) / self.lambda
+    }
+
+    /// Calculate the speedup at a given number of concurrent events relative to a single one,
+    /// `S(N) = X(N)/X(1)`.
+    #[must_use]
+    pub fn speedup_at_concurrency(&self, n: u32) -> f64 {
+        self.throughput_at_concurrency(n) / self.lambda
+    }
+
+    /// Calculate the speedup the system approaches as concurrency increases, `1/σ`.
+    #[must_use]
+    pub fn max_speedup(&self) -> f64 {
+        1.0 / self.serial_fraction
+    }
+
+    /// Calculate the throughput the system approaches as concurrency increases, `λ/σ`.
+    #[must_use]
+    pub fn max_throughput(&self) -> f64 {
+        self.lambda / self.serial_fraction
+    }
+}
+
+impl From<AmdahlModel> for Model {
+    fn from(model: AmdahlModel) -> Self {
+        Model { sigma: model.serial_fraction, kappa: 0.0, lambda: model.lambda }
+    }
+}
+
+impl ScalabilityModel for AmdahlModel {
+    fn throughput_at_concurrency(&self, n: u32) -> f64 {
+        AmdahlModel::throughput_at_concurrency(self, n)
+    }
+
+    fn throughput_at_concurrency_f64(&self, n: f64) -> f64 {
+        AmdahlModel::throughput_at_concurrency_f64(self, n)
+    }
+
+    fn parameters(&self) -> usize {
+        2
+    }
+}
+
+struct AmdahlFitter<'a>(&'a [Measurement]);
+
+impl Objective for AmdahlFitter<'_> {
+    fn number_of_points(&self) -> usize {
+        self.0.len()
+    }
+
+    fn residuals(&self, params: &[f64], residuals: &mut [f64]) {
+        let model = AmdahlModel { serial_fraction: params[0], lambda: params[1] };
+        for (r, m) in residuals.iter_mut().zip(self.0) {
+            *r = m.x - model.throughput_at_concurrency_f64(m.n);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn build() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let expected = Model::build_amdahl(&measurements);
+        let model = AmdahlModel::build(&measurements);
+        assert_relative_eq!(model.serial_fraction, expected.sigma, max_relative = 0.0001);
+        assert_relative_eq!(model.lambda, expected.lambda, max_relative = 0.0001);
+    }
+
+    #[test]
+    fn predictions() {
+        let model = AmdahlModel { serial_fraction: 0.1, lambda: 100.0 };
+        assert_relative_eq!(model.throughput_at_concurrency(91), 910.0);
+        assert_relative_eq!(model.latency_at_concurrency(91), 0.1);
+        assert_relative_eq!(model.speedup_at_concurrency(91), 9.1);
+        assert_relative_eq!(model.max_speedup(), 10.0);
+        assert_relative_eq!(model.max_throughput(), 1000.0);
+
+        let usl = Model::from(model);
+        assert_relative_eq!(usl.throughput_at_concurrency(91), 910.0);
+    }
+}
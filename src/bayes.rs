@@ -0,0 +1,238 @@
+use rand::Rng;
+
+use crate::{
+    percentile_interval, standard_normal, Measurement, Model, ModelFitter, StandardErrors,
+};
+
+/// A prior distribution over one of a model's parameters.
+#[derive(Debug, Copy, Clone)]
+pub enum Prior {
+    /// A uniform distribution over the given inclusive range. Either bound may be infinite.
+    Uniform {
+        /// The lower bound.
+        low: f64,
+        /// The upper bound.
+        high: f64,
+    },
+    /// A normal distribution with the given mean and standard deviation.
+    Normal {
+        /// The mean.
+        mean: f64,
+        /// The standard deviation.
+        sd: f64,
+    },
+}
+
+impl Prior {
+    fn ln_pdf(&self, v: f64) -> f64 {
+        match *self {
+            Prior::Uniform { low, high } if (low..=high).contains(&v) => 0.0,
+            Prior::Uniform { .. } => f64::NEG_INFINITY,
+            Prior::Normal { mean, sd } => -0.5 * ((v - mean) / sd).powi(2),
+        }
+    }
+}
+
+/// Options for sampling from the posterior distribution of a model via [Model::posterior].
+#[derive(Debug, Copy, Clone)]
+pub struct McmcOptions {
+    /// The number of posterior samples to return.
+    pub samples: usize,
+    /// The number of initial samples to discard while the chain converges.
+    pub burn_in: usize,
+    /// The number of chain steps per returned sample.
+    pub thin: usize,
+    /// The prior distribution of σ.
+    pub sigma: Prior,
+    /// The prior distribution of κ.
+    pub kappa: Prior,
+    /// The prior distribution of λ.
+    pub lambda: Prior,
+}
+
+impl Default for McmcOptions {
+    fn default() -> Self {
+        McmcOptions {
+            samples: 2_000,
+            burn_in: 1_000,
+            thin: 1,
+            sigma: Prior::Uniform { low: 0.0, high: 1.0 },
+            kappa: Prior::Uniform { low: 0.0, high: f64::INFINITY },
+            lambda: Prior::Uniform { low: 0.0, high: f64::INFINITY },
+        }
+    }
+}
+
+/// Samples from the posterior distribution of a model's parameters, via [Model::posterior].
+#[derive(Debug, Clone)]
+pub struct Posterior {
+    /// The posterior samples.
+    pub samples: Vec<Model>,
+    /// The fraction of proposed steps which the sampler accepted.
+    pub acceptance_rate: f64,
+}
+
+impl Posterior {
+    /// Calculate a credible interval for an arbitrary function of the model, e.g.
+    /// [Model::max_concurrency].
+    #[must_use]
+    pub fn interval(&self, confidence: f64, f: impl Fn(&Model) -> f64) -> (f64, f64) {
+        percentile_interval(&self.samples, confidence, f)
+    }
+
+    /// Calculate the posterior mean of an arbitrary function of the model.
+    #[must_use]
+    pub fn mean(&self, f: impl Fn(&Model) -> f64) -> f64 {
+        self.samples.iter().map(f).sum::<f64>() / self.samples.len() as f64
+    }
+}
+
+impl Model {
+    /// Sample from the posterior distribution of the model's parameters given the measurements.
+    ///
+    /// Uses a random-walk Metropolis sampler started from the least-squares fit, assuming normally
+    /// distributed throughput residuals with a variance estimated from that fit.
+    ///
+    /// Panics if `options.samples` or `options.thin` is zero.
+    #[must_use]
+    pub fn posterior(measurements: &[Measurement], options: &McmcOptions) -> Posterior {
+        Model::posterior_with_rng(measurements, options, &mut rand::thread_rng())
+    }
+
+    /// Like [Model::posterior], but using the given random number generator.
+    ///
+    /// Passing a seeded generator, e.g. `StdRng::seed_from_u64`, makes the results reproducible.
+    #[must_use]
+    pub fn posterior_with_rng(
+        measurements: &[Measurement],
+        options: &McmcOptions,
+        rng: &mut impl Rng,
+    ) -> Posterior {
+        assert!(options.samples > 0, "samples must be positive");
+        assert!(options.thin > 0, "thin must be positive");
+        let (model, report) = Model::build_with_report(measurements);
+        let errors = StandardErrors::from_report(&report);
+        let variance = report.chi_square / (measurements.len() - report.free_parameters) as f64;
+
+        let fitter = ModelFitter::new(measurements);
+        let ln_posterior = |p: &[f64; 3]| {
+            let ln_prior = options.sigma.ln_pdf(p[0])
+                + options.kappa.ln_pdf(p[1])
+                + options.lambda.ln_pdf(p[2]);
+            let ln_likelihood = -fitter.cost(p) / (2.0 * variance);
+            let v = ln_prior + ln_likelihood;
+            if v.is_nan() {
+                f64::NEG_INFINITY
+            } else {
+                v
+            }
+        };
+
+        // Draw correlated proposals from the parameters' covariance, using the optimal scaling for
+        // a three-dimensional Gaussian target.
+        let scale = 2.38 / 3f64.sqrt();
+        let mut current = [model.sigma, model.kappa, model.lambda];
+        let l = proposal_factor(&errors.covariance, &current);
+
+        let mut current_ln_p = ln_posterior(&current);
+        let mut accepted = 0;
+        let steps_total = options.burn_in + options.samples * options.thin;
+        let mut samples = Vec::with_capacity(options.samples);
+        for i in 0..steps_total {
+            let z = [standard_normal(rng), standard_normal(rng), standard_normal(rng)];
+            let mut proposal = current;
+            for (p, row) in proposal.iter_mut().zip(l) {
+                *p += scale * row.iter().zip(z).map(|(l, z)| l * z).sum::<f64>();
+            }
+            let proposal_ln_p = ln_posterior(&proposal);
+            if proposal_ln_p - current_ln_p >= rng.gen::<f64>().ln() {
+                current = proposal;
+                current_ln_p = proposal_ln_p;
+                accepted += 1;
+            }
+
+            if i >= options.burn_in && (i - options.burn_in) % options.thin == options.thin - 1 {
+                samples.push(Model { sigma: current[0], kappa: current[1], lambda: current[2] });
+            }
+        }
+
+        Posterior { samples, acceptance_rate: accepted as f64 / steps_total as f64 }
+    }
+}
+
+/// Calculate the factor by which proposals are drawn from the covariance of the parameters.
+///
+/// If the covariance is unusable, e.g. NaN because the fit fell back to Nelder-Mead or singular
+/// because a parameter is pinned, proposals are instead drawn independently with a standard
+/// deviation of 1% of each parameter.
+fn proposal_factor(covariance: &[[f64; 3]; 3], params: &[f64; 3]) -> [[f64; 3]; 3] {
+    let l = cholesky(covariance);
+    if (0..3).all(|i| l[i][i] > 0.0 && l[i].iter().all(|v| v.is_finite())) {
+        return l;
+    }
+    let mut l = [[0.0; 3]; 3];
+    for (i, p) in params.iter().enumerate() {
+        l[i][i] = (0.01 * p.abs()).max(f64::EPSILON.sqrt());
+    }
+    l
+}
+
+/// Calculate the lower-triangular Cholesky factor of a symmetric positive-definite matrix.
+fn cholesky(a: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    let mut l = [[0.0; 3]; 3];
+    for i in 0..3 {
+        for j in 0..=i {
+            let sum: f64 = (0..j).map(|k| l[i][k] * l[j][k]).sum();
+            l[i][j] =
+                if i == j { (a[i][i] - sum).max(0.0).sqrt() } else { (a[i][j] - sum) / l[j][j] };
+        }
+    }
+    l
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn posterior() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let model = Model::build(&measurements);
+        let mut rng = StdRng::seed_from_u64(0xDEADBEEF);
+        let posterior = Model::posterior_with_rng(&measurements, &McmcOptions::default(), &mut rng);
+
+        assert_eq!(posterior.samples.len(), 2_000);
+        assert!(posterior.acceptance_rate > 0.1 && posterior.acceptance_rate < 0.9);
+        assert_relative_eq!(posterior.mean(|m| m.kappa), model.kappa, max_relative = 0.1);
+        assert_relative_eq!(posterior.mean(|m| m.lambda), model.lambda, max_relative = 0.01);
+
+        let (low, high) = posterior.interval(0.95, |m| m.sigma);
+        assert!(low < model.sigma && model.sigma < high);
+    }
+
+    #[test]
+    fn proposal_factor_fallback() {
+        let params = [0.02, 0.0, 1000.0];
+        let diagonal = [[0.0002, 0.0, 0.0], [0.0, f64::EPSILON.sqrt(), 0.0], [0.0, 0.0, 10.0]];
+        assert_eq!(proposal_factor(&[[f64::NAN; 3]; 3], &params), diagonal);
+
+        let singular = [[1.0, 0.0, 0.5], [0.0, 0.0, 0.0], [0.5, 0.0, 1.0]];
+        assert_eq!(proposal_factor(&singular, &params), diagonal);
+
+        let identity = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        assert_eq!(proposal_factor(&identity, &params), identity);
+    }
+
+    #[test]
+    #[should_panic(expected = "thin must be positive")]
+    fn posterior_without_thinning() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let options = McmcOptions { thin: 0, ..Default::default() };
+        let _ = Model::posterior(&measurements, &options);
+    }
+}
@@ -0,0 +1,137 @@
+use rand::Rng;
+
+use crate::{map_fits, percentile_interval, Measurement, Model, ModelFitter, MIN_MEASUREMENTS};
+
+/// A set of models fitted to bootstrap resamples of a set of measurements, via [Model::bootstrap].
+///
+/// ```
+/// use usl::{Model, Measurement};
+///
+/// let measurements: Vec<Measurement> =
+///     [(1, 65.0), (18, 996.0), (36, 1652.0), (72, 1853.0), (108, 1829.0), (144, 1775.0)]
+///         .iter()
+///         .map(|&m| m.into())
+///         .collect();
+/// let bootstrap = Model::bootstrap(&measurements, 100);
+/// let (low, high) = bootstrap.interval(0.95, |m| m.max_throughput());
+/// println!("X{{max}} is between {} and {}", low, high);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bootstrap {
+    /// The models fitted to each resample. Resamples which could not be fitted are omitted.
+    pub models: Vec<Model>,
+}
+
+impl Bootstrap {
+    /// Calculate a percentile confidence interval for an arbitrary function of the model, e.g.
+    /// [Model::max_throughput].
+    ///
+    /// Panics if `confidence` is not between 0 and 1, or if there are no models.
+    #[must_use]
+    pub fn interval(&self, confidence: f64, f: impl Fn(&Model) -> f64) -> (f64, f64) {
+        assert!(!self.models.is_empty(), "no bootstrap models");
+        percentile_interval(&self.models, confidence, f)
+    }
+
+    /// Calculate a percentile confidence interval for σ.
+    #[must_use]
+    pub fn sigma(&self, confidence: f64) -> (f64, f64) {
+        self.interval(confidence, |m| m.sigma)
+    }
+
+    /// Calculate a percentile confidence interval for κ.
+    #[must_use]
+    pub fn kappa(&self, confidence: f64) -> (f64, f64) {
+        self.interval(confidence, |m| m.kappa)
+    }
+
+    /// Calculate a percentile confidence interval for λ.
+    #[must_use]
+    pub fn lambda(&self, confidence: f64) -> (f64, f64) {
+        self.interval(confidence, |m| m.lambda)
+    }
+}
+
+impl Model {
+    /// Fit models to `n_samples` bootstrap resamples of the given measurements.
+    ///
+    /// Each resample is drawn with replacement from the measurements and has the same size. The
+    /// spread of the resulting models estimates the uncertainty in a single fit, which is essential
+    /// for knowing whether two sets of measurements describe meaningfully different systems.
+    #[must_use]
+    pub fn bootstrap(measurements: &[Measurement], n_samples: usize) -> Bootstrap {
+        Model::bootstrap_with_rng(measurements, n_samples, &mut rand::thread_rng())
+    }
+
+    /// Like [Model::bootstrap], but using the given random number generator.
+    ///
+    /// Passing a seeded generator, e.g. `StdRng::seed_from_u64`, makes the results reproducible.
+    #[must_use]
+    pub fn bootstrap_with_rng(
+        measurements: &[Measurement],
+        n_samples: usize,
+        rng: &mut impl Rng,
+    ) -> Bootstrap {
+        assert!(
+            measurements.len() >= MIN_MEASUREMENTS,
+            "must have at least {} measurements",
+            MIN_MEASUREMENTS
+        );
+        // Draw every resample up front, so the result is the same whether or not the fits are run
+        // in parallel.
+        let resamples: Vec<Vec<Measurement>> = (0..n_samples)
+            .map(|_| {
+                (0..measurements.len())
+                    .map(|_| measurements[rng.gen_range(0..measurements.len())])
+                    .collect()
+            })
+            .collect();
+        let models =
+            map_fits(&resamples, |resample| Model::try_fit(&ModelFitter::new(resample), None).ok());
+        Bootstrap { models: models.into_iter().flatten().collect() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn bootstrap() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let model = Model::build(&measurements);
+        let mut rng = StdRng::seed_from_u64(0xDEADBEEF);
+        let bootstrap = Model::bootstrap_with_rng(&measurements, 200, &mut rng);
+
+        assert_eq!(bootstrap.models.len(), 200);
+        for (interval, value) in [
+            (bootstrap.sigma(0.95), model.sigma),
+            (bootstrap.kappa(0.95), model.kappa),
+            (bootstrap.lambda(0.95), model.lambda),
+            (bootstrap.interval(0.95, Model::max_throughput), model.max_throughput()),
+        ] {
+            assert!(interval.0 < value && value < interval.1, "{:?} {}", interval, value);
+        }
+
+        let narrow = bootstrap.kappa(0.5);
+        let wide = bootstrap.kappa(0.95);
+        assert!(wide.0 < narrow.0 && narrow.1 < wide.1);
+    }
+
+    #[test]
+    fn bootstrap_is_reproducible() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let a = Model::bootstrap_with_rng(&measurements, 20, &mut StdRng::seed_from_u64(1));
+        let b = Model::bootstrap_with_rng(&measurements, 20, &mut StdRng::seed_from_u64(1));
+
+        let params = |b: &Bootstrap| -> Vec<(f64, f64, f64)> {
+            b.models.iter().map(|m| (m.sigma, m.kappa, m.lambda)).collect()
+        };
+        assert_eq!(params(&a), params(&b));
+    }
+}
@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use crate::{Measurement, TimedMeasurement};
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// The measurements taken within one fixed window of time, via [TimedMeasurement::bucket].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bucket {
+    /// The start of the window, inclusive.
+    pub start: Duration,
+    /// The end of the window, exclusive.
+    pub end: Duration,
+    /// The number of measurements in the window.
+    pub len: usize,
+    /// The average of the measurements in the window. Its concurrency and throughput are the means
+    /// of the measurements', and its latency is derived from them via Little's Law.
+    pub measurement: Measurement,
+}
+
+impl TimedMeasurement {
+    /// Group a time series of measurements into consecutive windows of the given width, aligned to
+    /// multiples of it, and average the measurements in each window.
+    ///
+    /// This turns high-frequency production telemetry (e.g. one sample per second) into fewer,
+    /// less noisy measurements. Windows without measurements are omitted, and the rest are
+    /// returned in order of time.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use usl::{Measurement, TimedMeasurement};
+    ///
+    /// let samples: Vec<TimedMeasurement> = (0..600)
+    ///     .map(|s| {
+    ///         let m = Measurement::concurrency_and_throughput(10 + s / 60, 1000.0);
+    ///         TimedMeasurement::new(Duration::from_secs(s.into()), m)
+    ///     })
+    ///     .collect();
+    /// let buckets = TimedMeasurement::bucket(&samples, Duration::from_secs(60));
+    /// assert_eq!(buckets.len(), 10);
+    /// ```
+    #[must_use]
+    pub fn bucket(measurements: &[TimedMeasurement], width: Duration) -> Vec<Bucket> {
+        assert!(!width.is_zero(), "width must be non-zero");
+        let mut sorted = measurements.to_vec();
+        sorted.sort_by_key(|m| m.time);
+
+        let mut buckets: Vec<Bucket> = Vec::new();
+        for m in sorted {
+            let nanos = m.time.as_nanos() / width.as_nanos() * width.as_nanos();
+            let start =
+                Duration::new((nanos / NANOS_PER_SEC) as u64, (nanos % NANOS_PER_SEC) as u32);
+            match buckets.last_mut() {
+                Some(b) if b.start == start => {
+                    b.len += 1;
+                    b.measurement.n += m.measurement.n;
+                    b.measurement.x += m.measurement.x;
+                }
+                _ => buckets.push(Bucket {
+                    start,
+                    end: start + width,
+                    len: 1,
+                    measurement: m.measurement,
+                }),
+            }
+        }
+
+        for b in &mut buckets {
+            let len = b.len as f64;
+            b.measurement.n /= len;
+            b.measurement.x /= len;
+            b.measurement.r = b.measurement.n / b.measurement.x;
+        }
+        buckets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn bucket() {
+        let measurement = |s: u64, n: u32, x: f64| {
+            TimedMeasurement::new(
+                Duration::from_secs(s),
+                Measurement::concurrency_and_throughput(n, x),
+            )
+        };
+        let measurements = [
+            measurement(125, 30, 300.0),
+            measurement(61, 10, 100.0),
+            measurement(119, 20, 300.0),
+            measurement(7, 5, 50.0),
+        ];
+
+        let buckets = TimedMeasurement::bucket(&measurements, Duration::from_secs(60));
+        assert_eq!(buckets.len(), 3);
+        assert_eq!(buckets.iter().map(|b| b.len).collect::<Vec<_>>(), vec![1, 2, 1]);
+        assert_eq!(buckets[1].start, Duration::from_secs(60));
+        assert_eq!(buckets[1].end, Duration::from_secs(120));
+        assert_relative_eq!(buckets[1].measurement.n, 15.0);
+        assert_relative_eq!(buckets[1].measurement.x, 200.0);
+        assert_relative_eq!(buckets[1].measurement.r, 0.075);
+        assert_eq!(buckets[2].start, Duration::from_secs(120));
+    }
+}
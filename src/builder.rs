@@ -0,0 +1,226 @@
+use crate::{median, FitOptions, FitWarning, Measurement, Model, ModelFitter, MIN_MEASUREMENTS};
+
+/// A way of collapsing replicate measurements at the same concurrency level into one.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Aggregation {
+    /// The arithmetic mean.
+    Mean,
+    /// The median.
+    Median,
+    /// The mean after discarding the given fraction of the lowest and highest values, e.g. `0.1`
+    /// discards the lowest 10% and the highest 10%.
+    TrimmedMean(f64),
+}
+
+impl Aggregation {
+    fn apply(&self, values: &mut [f64]) -> f64 {
+        values.sort_by(f64::total_cmp);
+        let values = match *self {
+            Aggregation::Mean => values,
+            Aggregation::Median => return median(values),
+            Aggregation::TrimmedMean(fraction) => {
+                assert!((0.0..0.5).contains(&fraction), "trim fraction must be in [0, 0.5)");
+                let trim = (values.len() as f64 * fraction).floor() as usize;
+                &values[trim..values.len() - trim]
+            }
+        };
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+/// A builder for models which need preprocessing of their measurements or non-default fitting
+/// options.
+///
+/// ```
+/// use usl::{Aggregation, Measurement, ModelBuilder};
+///
+/// let measurements: Vec<Measurement> = [
+///     (1, 65.0), (1, 67.0), (18, 996.0), (18, 990.0), (36, 1652.0), (36, 1650.0),
+///     (72, 1853.0), (72, 1849.0), (108, 1829.0), (108, 1833.0), (144, 1775.0), (144, 1771.0),
+/// ]
+/// .iter()
+/// .map(|&m| m.into())
+/// .collect();
+/// let model = ModelBuilder::new().aggregate(Aggregation::Median).build(&measurements);
+/// ```
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ModelBuilder {
+    options: FitOptions,
+    aggregation: Option<Aggregation>,
+    underdetermined: bool,
+    min_levels: Option<usize>,
+}
+
+impl ModelBuilder {
+    /// Create a new builder which fits models as [Model::build] does.
+    #[must_use]
+    pub fn new() -> ModelBuilder {
+        ModelBuilder::default()
+    }
+
+    /// Use the given options when fitting the model.
+    #[must_use]
+    pub const fn options(mut self, options: FitOptions) -> ModelBuilder {
+        self.options = options;
+        self
+    }
+
+    /// Collapse measurements with the same concurrency level into a single measurement before
+    /// fitting the model. Throughput and latency are aggregated independently.
+    #[must_use]
+    pub const fn aggregate(mut self, aggregation: Aggregation) -> ModelBuilder {
+        self.aggregation = Some(aggregation);
+        self
+    }
+
+    /// Allow models to be fitted to fewer than [MIN_MEASUREMENTS] measurements, down to one per
+    /// parameter. Such models are reported via [FitWarning::Underdetermined] by
+    /// [ModelBuilder::build_with_warnings].
+    #[must_use]
+    pub const fn allow_underdetermined(mut self, allow: bool) -> ModelBuilder {
+        self.underdetermined = allow;
+        self
+    }
+
+    /// Check whether the measurements cover enough concurrency levels for the model's peak to be
+    /// meaningful, requiring at least `min_levels` distinct levels. Problems are reported via
+    /// [Model::coverage_warnings] by [ModelBuilder::build_with_warnings].
+    #[must_use]
+    pub const fn check_coverage(mut self, min_levels: usize) -> ModelBuilder {
+        self.min_levels = Some(min_levels);
+        self
+    }
+
+    /// Build a model from the given measurements.
+    #[must_use]
+    pub fn build(&self, measurements: &[Measurement]) -> Model {
+        self.build_with_warnings(measurements).0
+    }
+
+    /// Build a model from the given measurements, returning any caveats about the fit alongside
+    /// it.
+    #[must_use]
+    pub fn build_with_warnings(&self, measurements: &[Measurement]) -> (Model, Vec<FitWarning>) {
+        let measurements = match self.aggregation {
+            Some(aggregation) => aggregate(measurements, aggregation),
+            None => measurements.to_vec(),
+        };
+        let fitter = ModelFitter { options: self.options, ..ModelFitter::new(&measurements) };
+
+        let mut warnings = Vec::new();
+        let model = if self.underdetermined && measurements.len() < MIN_MEASUREMENTS {
+            assert!(
+                measurements.len() >= PARAMETERS,
+                "must have at least {} measurements",
+                PARAMETERS
+            );
+            warnings.push(FitWarning::Underdetermined { measurements: measurements.len() });
+            match Model::try_fit(&fitter, None) {
+                Ok(model) => model,
+                Err(err) => panic!("{}", err),
+            }
+        } else {
+            Model::fit(fitter, None)
+        };
+        warnings.extend(model.warnings(&measurements));
+        if let Some(min_levels) = self.min_levels {
+            warnings.extend(model.coverage_warnings(&measurements, min_levels));
+        }
+        (model, warnings)
+    }
+}
+
+/// The number of parameters fitted by the model, and so the fewest measurements it can be fitted to.
+const PARAMETERS: usize = 3;
+
+/// Collapse measurements with the same concurrency level, returning them in order of concurrency.
+fn aggregate(measurements: &[Measurement], aggregation: Aggregation) -> Vec<Measurement> {
+    let mut sorted = measurements.to_vec();
+    sorted.sort_by(|a, b| a.n.total_cmp(&b.n));
+    sorted
+        .chunk_by(|a, b| a.n == b.n)
+        .map(|group| {
+            let mut x: Vec<f64> = group.iter().map(|m| m.x).collect();
+            let mut r: Vec<f64> = group.iter().map(|m| m.r).collect();
+            Measurement {
+                n: group[0].n,
+                x: aggregation.apply(&mut x),
+                r: aggregation.apply(&mut r),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn aggregations() {
+        let values = [5.0, 1.0, 2.0, 3.0, 100.0];
+        assert_relative_eq!(Aggregation::Mean.apply(&mut values.clone()), 22.2);
+        assert_relative_eq!(Aggregation::Median.apply(&mut values.clone()), 3.0);
+        assert_relative_eq!(Aggregation::TrimmedMean(0.2).apply(&mut values.clone()), 10.0 / 3.0);
+    }
+
+    #[test]
+    fn build_aggregated() {
+        let expected: Model = MEASUREMENTS.iter().collect();
+        let measurements: Vec<Measurement> = MEASUREMENTS
+            .iter()
+            .flat_map(|&(n, x)| [(n, x * 0.9), (n, x * 1.1), (n, x)])
+            .map(|m| m.into())
+            .collect();
+
+        let model = ModelBuilder::new().aggregate(Aggregation::Median).build(&measurements);
+        assert_relative_eq!(model.sigma, expected.sigma, max_relative = 0.00001);
+        assert_relative_eq!(model.kappa, expected.kappa, max_relative = 0.00001);
+        assert_relative_eq!(model.lambda, expected.lambda, max_relative = 0.00001);
+    }
+
+    #[test]
+    fn build_underdetermined() {
+        let expected: Model = MEASUREMENTS.iter().collect();
+        let measurements: Vec<Measurement> =
+            MEASUREMENTS.iter().step_by(7).map(|&m| m.into()).collect();
+        assert_eq!(measurements.len(), 5);
+
+        let (model, warnings) =
+            ModelBuilder::new().allow_underdetermined(true).build_with_warnings(&measurements);
+        assert_eq!(warnings, vec![FitWarning::Underdetermined { measurements: 5 }]);
+        assert_relative_eq!(model.kappa, expected.kappa, max_relative = 0.1);
+        assert_relative_eq!(model.lambda, expected.lambda, max_relative = 0.1);
+    }
+
+    #[test]
+    fn build_with_coverage_warnings() {
+        let measurements: Vec<Measurement> =
+            MEASUREMENTS.iter().take(20).map(|&m| m.into()).collect();
+
+        let (_, unchecked) = ModelBuilder::new().build_with_warnings(&measurements);
+        let (model, warnings) =
+            ModelBuilder::new().check_coverage(25).build_with_warnings(&measurements);
+        assert_eq!(warnings[..unchecked.len()], unchecked);
+        assert_eq!(
+            warnings[unchecked.len()..],
+            [
+                FitWarning::TooFewLevels { levels: 20, required: 25 },
+                FitWarning::PeakNotBracketed {
+                    peak: ((1.0 - model.sigma) / model.kappa).sqrt(),
+                    measured: (1.0, 20.0)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "must have at least 6 measurements")]
+    fn build_underdetermined_disallowed() {
+        let measurements: Vec<Measurement> =
+            MEASUREMENTS.iter().step_by(7).map(|&m| m.into()).collect();
+        let _ = ModelBuilder::new().build(&measurements);
+    }
+}
@@ -0,0 +1,363 @@
+//! Functions for planning the capacity of systems of independently modeled nodes.
+
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use crate::Model;
+
+/// A capacity plan for serving a forecast load, via [plan].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Plan {
+    /// The number of nodes required.
+    pub nodes: u32,
+    /// The concurrency each node is run at, which may be lower than requested in order to meet a
+    /// latency objective.
+    pub per_node_concurrency: u32,
+    /// The expected throughput of each node at that concurrency.
+    pub per_node_throughput: f64,
+    /// The expected mean latency of each node at that concurrency, in seconds.
+    pub latency: f64,
+    /// The fraction of the nodes' total throughput the forecast load will use.
+    pub utilization: f64,
+}
+
+/// Calculate the number of nodes, each described by the given model, needed to serve a forecast
+/// arrival rate (in events/sec) when each node is run at the given concurrency.
+///
+/// If a maximum latency is given, each node's concurrency is reduced if necessary so that its
+/// expected mean latency is within it. Returns `None` if the maximum latency cannot be met at any
+/// concurrency.
+///
+/// ```
+/// use usl::Model;
+/// use usl::capacity::plan;
+///
+/// let model = Model { sigma: 0.02, kappa: 0.0001, lambda: 1000.0 };
+/// let plan = plan(&model, 100_000.0, 32, None).unwrap();
+/// println!("{} nodes at {:.0}% utilization", plan.nodes, plan.utilization * 100.0);
+/// ```
+#[must_use]
+pub fn plan(
+    model: &Model,
+    arrival_rate: f64,
+    per_node_concurrency: u32,
+    max_latency: Option<Duration>,
+) -> Option<Plan> {
+    assert!(per_node_concurrency > 0, "per-node concurrency must be positive");
+    let n = match max_latency {
+        Some(slo) => per_node_concurrency.min(model.max_concurrency_within_latency(slo)?.0),
+        None => per_node_concurrency,
+    };
+    let x = model.throughput_at_concurrency(n);
+    let nodes = ((arrival_rate / x).ceil() as u32).max(1);
+    Some(Plan {
+        nodes,
+        per_node_concurrency: n,
+        per_node_throughput: x,
+        latency: model.latency_at_concurrency(n),
+        utilization: arrival_rate / (f64::from(nodes) * x),
+    })
+}
+
+/// A dimension along which a system can be scaled.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Dimension {
+    /// Adding concurrency to a single node, e.g. threads.
+    Up,
+    /// Adding nodes.
+    Out,
+}
+
+/// A comparison of scaling up with scaling out, via [scale_up_or_out].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScalingComparison {
+    /// The throughput gained by adding one unit of concurrency to the current node.
+    pub scale_up: f64,
+    /// The throughput gained by adding one node to the current number of nodes.
+    pub scale_out: f64,
+    /// The dimension which gains more throughput per added unit.
+    pub better: Dimension,
+    /// The level of the better dimension at which adding another unit of it first gains less
+    /// throughput than adding a unit of the other dimension would now, or `None` if that never
+    /// happens.
+    pub crossover: Option<u32>,
+}
+
+/// Compare the throughput gained by scaling up with that gained by scaling out, given a model of
+/// throughput as a function of concurrency on a single node, one of throughput as a function of
+/// the number of nodes, and the current concurrency and number of nodes.
+///
+/// ```
+/// use usl::Model;
+/// use usl::capacity::scale_up_or_out;
+///
+/// let up = Model { sigma: 0.05, kappa: 0.001, lambda: 1000.0 };
+/// let out = Model { sigma: 0.01, kappa: 0.0001, lambda: 8000.0 };
+/// let comparison = scale_up_or_out(&up, &out, 16, 4);
+/// println!("scale {:?}", comparison.better);
+/// ```
+#[must_use]
+pub fn scale_up_or_out(up: &Model, out: &Model, concurrency: u32, nodes: u32) -> ScalingComparison {
+    let marginal =
+        |m: &Model, n: u32| m.throughput_at_concurrency(n + 1) - m.throughput_at_concurrency(n);
+    let scale_up = marginal(up, concurrency);
+    let scale_out = marginal(out, nodes);
+    let (better, crossover) = if scale_up >= scale_out {
+        (Dimension::Up, crossover(up, scale_out))
+    } else {
+        (Dimension::Out, crossover(out, scale_up))
+    };
+    ScalingComparison { scale_up, scale_out, better, crossover }
+}
+
+/// Find the level at which the model's marginal throughput first drops below the given marginal
+/// throughput of the other dimension.
+fn crossover(model: &Model, other: f64) -> Option<u32> {
+    if other <= 0.0 {
+        return None;
+    }
+    model.knee(other / model.lambda)
+}
+
+/// The costs and revenues of running a system, for [roi].
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Economics {
+    /// The cost of running one unit of concurrency (e.g. one worker) for an hour.
+    pub cost_per_worker_hour: f64,
+    /// The value of each successfully served event.
+    pub value_per_request: f64,
+}
+
+/// The hourly costs and revenues of running a system at a particular concurrency, via [roi].
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NetValue {
+    /// The number of concurrent events, e.g. workers.
+    pub concurrency: u32,
+    /// The expected throughput, in events/sec.
+    pub throughput: f64,
+    /// The value of the events served in an hour.
+    pub revenue: f64,
+    /// The cost of running the workers for an hour.
+    pub cost: f64,
+    /// The revenue less the cost.
+    pub net: f64,
+}
+
+/// The return on investment of running a system at each of a range of concurrency levels, via
+/// [roi].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Roi {
+    /// The net value at each concurrency in the range, in order.
+    pub sweep: Vec<NetValue>,
+    /// The concurrency in the range with the highest net value.
+    pub best: NetValue,
+}
+
+/// Calculate the hourly net value of running the system described by the given model at each
+/// concurrency in the given range, and find the profit-maximizing concurrency.
+///
+/// Because each additional worker adds less throughput than the last, the most profitable
+/// concurrency is usually well below the one with the highest throughput.
+///
+/// Panics if the range is empty.
+///
+/// ```
+/// use usl::Model;
+/// use usl::capacity::{roi, Economics};
+///
+/// let model = Model { sigma: 0.02, kappa: 0.0001, lambda: 1000.0 };
+/// let economics = Economics { cost_per_worker_hour: 0.50, value_per_request: 0.000_01 };
+/// let roi = roi(&model, &economics, 1..=128);
+/// println!("run {} workers for ${:.2}/hour", roi.best.concurrency, roi.best.net);
+/// ```
+#[must_use]
+pub fn roi(model: &Model, economics: &Economics, range: RangeInclusive<u32>) -> Roi {
+    let sweep: Vec<NetValue> = model
+        .predict_series(range)
+        .map(|p| {
+            let revenue = p.throughput * 3600.0 * economics.value_per_request;
+            let cost = f64::from(p.concurrency) * economics.cost_per_worker_hour;
+            NetValue {
+                concurrency: p.concurrency,
+                throughput: p.throughput,
+                revenue,
+                cost,
+                net: revenue - cost,
+            }
+        })
+        .collect();
+    let best = *sweep.iter().max_by(|a, b| a.net.total_cmp(&b.net)).expect("empty range");
+    Roi { sweep, best }
+}
+
+/// The throughput gained by adding a fixed number of workers, via [break_even].
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BreakEven {
+    /// The expected throughput gained by the additional workers, in events/sec.
+    pub gain: f64,
+    /// Whether the gain is at least the break-even threshold.
+    pub clears: bool,
+    /// The concurrency at and beyond which adding the same number of workers never gains at least
+    /// the break-even threshold, or `None` if that never happens.
+    pub limit: Option<u32>,
+}
+
+/// Calculate the throughput gained by adding `increment` workers to a system currently running at
+/// the given concurrency, and whether it clears the given break-even threshold (e.g. the
+/// throughput needed to pay for the workers, in events/sec).
+///
+/// Panics if `increment` is zero.
+///
+/// ```
+/// use usl::Model;
+/// use usl::capacity::break_even;
+///
+/// let model = Model { sigma: 0.02, kappa: 0.0001, lambda: 1000.0 };
+/// let b = break_even(&model, 32, 4, 1_000.0);
+/// println!("+4 workers gains {:.0}/sec; worth it: {}", b.gain, b.clears);
+/// ```
+#[must_use]
+pub fn break_even(model: &Model, concurrency: u32, increment: u32, threshold: f64) -> BreakEven {
+    assert!(increment > 0, "increment must be positive");
+    let gain = |n: u32| {
+        let n = f64::from(n);
+        model.throughput_at_concurrency_f64(n + f64::from(increment))
+            - model.throughput_at_concurrency_f64(n)
+    };
+
+    // Find an upper bound by doubling, then bisect to find the first concurrency below the
+    // threshold, relying on the gain decreasing as concurrency increases.
+    let limit = if gain(0) < threshold {
+        Some(0)
+    } else {
+        let mut high = 1;
+        while high < u32::MAX && gain(high) >= threshold {
+            high = high.saturating_mul(2);
+        }
+        (gain(high) < threshold).then(|| {
+            let mut low = high / 2;
+            while high - low > 1 {
+                let mid = low + (high - low) / 2;
+                if gain(mid) < threshold {
+                    high = mid;
+                } else {
+                    low = mid;
+                }
+            }
+            high
+        })
+    };
+
+    let gain = gain(concurrency);
+    BreakEven { gain, clears: gain >= threshold, limit }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn unconstrained() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        let x = model.throughput_at_concurrency(20);
+
+        let p = plan(&model, x * 10.5, 20, None).unwrap();
+        assert_eq!(p.nodes, 11);
+        assert_eq!(p.per_node_concurrency, 20);
+        assert_relative_eq!(p.per_node_throughput, x);
+        assert_relative_eq!(p.utilization, 10.5 / 11.0);
+
+        assert_eq!(plan(&model, 1.0, 20, None).unwrap().nodes, 1);
+    }
+
+    #[test]
+    fn within_latency() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        let x = model.throughput_at_concurrency(23);
+
+        let p = plan(&model, x * 10.0, 30, Some(Duration::from_millis(2))).unwrap();
+        assert_eq!(p.per_node_concurrency, 23);
+        assert_eq!(p.nodes, 10);
+        assert!(p.latency <= 0.002);
+
+        assert!(plan(&model, x, 30, Some(Duration::from_micros(900))).is_none());
+    }
+
+    #[test]
+    fn scale_up_or_out() {
+        let up: Model = MEASUREMENTS.iter().collect();
+        let out = Model { sigma: 0.01, kappa: 0.0001, lambda: 8000.0 };
+
+        let comparison = super::scale_up_or_out(&up, &out, 16, 4);
+        assert_relative_eq!(
+            comparison.scale_up,
+            up.throughput_at_concurrency(17) - up.throughput_at_concurrency(16)
+        );
+        assert_relative_eq!(
+            comparison.scale_out,
+            out.throughput_at_concurrency(5) - out.throughput_at_concurrency(4)
+        );
+        assert_eq!(comparison.better, Dimension::Out);
+        let crossover = comparison.crossover.expect("should cross over");
+        assert!(
+            out.throughput_at_concurrency(crossover + 1) - out.throughput_at_concurrency(crossover)
+                < comparison.scale_up
+        );
+
+        let comparison = super::scale_up_or_out(&up, &out, 1, 150);
+        assert_eq!(comparison.better, Dimension::Up);
+        assert_eq!(comparison.crossover, None);
+    }
+
+    #[test]
+    fn roi() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        let economics = Economics { cost_per_worker_hour: 1.0, value_per_request: 0.000_01 };
+        let roi = super::roi(&model, &economics, 1..=64);
+
+        assert_eq!(roi.sweep.len(), 64);
+        let at_20 = roi.sweep[19];
+        assert_eq!(at_20.concurrency, 20);
+        assert_relative_eq!(at_20.revenue, model.throughput_at_concurrency(20) * 0.036);
+        assert_relative_eq!(at_20.cost, 20.0);
+        assert_relative_eq!(at_20.net, at_20.revenue - at_20.cost);
+
+        // The last worker hired must pay for itself, and the next must not.
+        let n = roi.best.concurrency;
+        assert!(n < 36);
+        assert!(model.marginal_throughput(n - 1) * 0.036 >= 1.0);
+        assert!(model.marginal_throughput(n) * 0.036 < 1.0);
+    }
+
+    #[test]
+    fn break_even() {
+        let model: Model = MEASUREMENTS.iter().collect();
+
+        let b = super::break_even(&model, 16, 4, 1_000.0);
+        assert_relative_eq!(
+            b.gain,
+            model.throughput_at_concurrency(20) - model.throughput_at_concurrency(16)
+        );
+        assert!(b.clears);
+
+        let limit = b.limit.expect("should have a limit");
+        let gain = |n| model.throughput_at_concurrency(n + 4) - model.throughput_at_concurrency(n);
+        assert!(gain(limit - 1) >= 1_000.0);
+        assert!(gain(limit) < 1_000.0);
+        assert!(!super::break_even(&model, limit, 4, 1_000.0).clears);
+
+        let linear = Model { sigma: 0.0, kappa: 0.0, lambda: 1000.0 };
+        assert_eq!(super::break_even(&linear, 16, 4, 1_000.0).limit, None);
+        assert_eq!(super::break_even(&linear, 16, 4, 5_000.0).limit, Some(0));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::Model;
+
+/// A cluster of identical nodes, each described by a per-node [Model], which scale out according
+/// to the Universal Scalability Law at the node level.
+///
+/// The cluster's throughput is the per-node throughput multiplied by `M/(1+σ(M-1)+κM(M-1))`,
+/// where `M` is the number of nodes and σ and κ are the penalties for contention and coherency
+/// between nodes, e.g. for a shared database or for replication traffic.
+///
+/// ```
+/// use usl::{Cluster, Model};
+///
+/// let node = Model { sigma: 0.02, kappa: 0.0001, lambda: 1000.0 };
+/// let cluster = Cluster { node, nodes: 8, sigma: 0.0, kappa: 0.001 };
+/// println!("X = {}", cluster.throughput_at_concurrency(32));
+/// ```
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cluster {
+    /// The model of each node.
+    pub node: Model,
+    /// The number of nodes.
+    pub nodes: u32,
+    /// The coefficient of contention between nodes.
+    pub sigma: f64,
+    /// The coefficient of coherency between nodes.
+    pub kappa: f64,
+}
+
+impl Cluster {
+    /// Calculate the expected throughput of the whole cluster given the number of concurrent
+    /// events on each node.
+    #[must_use]
+    pub fn throughput_at_concurrency(&self, n: u32) -> f64 {
+        self.scale_out() * self.node.throughput_at_concurrency(n)
+    }
+
+    /// Calculate the expected mean latency of the cluster given the number of concurrent events
+    /// on each node, via Little's Law.
+    #[must_use]
+    pub fn latency_at_concurrency(&self, n: u32) -> f64 {
+        f64::from(self.nodes) * f64::from(n) / self.throughput_at_concurrency(n)
+    }
+
+    /// Calculate the number of nodes beyond which adding nodes reduces the cluster's throughput,
+    /// or `None` if there is no such limit.
+    #[must_use]
+    pub fn max_nodes(&self) -> Option<u32> {
+        Model { sigma: self.sigma, kappa: self.kappa, lambda: 1.0 }.checked_max_concurrency()
+    }
+
+    /// The effective number of nodes, after penalties for contention and coherency.
+    fn scale_out(&self) -> f64 {
+        Model { sigma: self.sigma, kappa: self.kappa, lambda: 1.0 }
+            .throughput_at_concurrency(self.nodes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn cluster() {
+        let node: Model = MEASUREMENTS.iter().collect();
+
+        let single = Cluster { node, nodes: 1, sigma: 0.1, kappa: 0.01 };
+        assert_relative_eq!(
+            single.throughput_at_concurrency(20),
+            node.throughput_at_concurrency(20)
+        );
+        assert_relative_eq!(single.latency_at_concurrency(20), node.latency_at_concurrency(20));
+
+        let linear = Cluster { node, nodes: 4, sigma: 0.0, kappa: 0.0 };
+        assert_relative_eq!(
+            linear.throughput_at_concurrency(20),
+            4.0 * node.throughput_at_concurrency(20)
+        );
+        assert_relative_eq!(linear.latency_at_concurrency(20), node.latency_at_concurrency(20));
+        assert_eq!(linear.max_nodes(), None);
+
+        let coherent = Cluster { node, nodes: 4, sigma: 0.0, kappa: 0.01 };
+        assert_relative_eq!(
+            coherent.throughput_at_concurrency(20),
+            4.0 / 1.12 * node.throughput_at_concurrency(20)
+        );
+        assert_eq!(coherent.max_nodes(), Some(10));
+    }
+}
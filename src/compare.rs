@@ -0,0 +1,320 @@
+//! Functions for comparing models with one another.
+
+use crate::{f_survival, Measurement, Model, ModelDiff, ScalabilityModel};
+
+/// Information criteria scores for a model, via [information_criteria]. For each criterion, lower
+/// scores indicate a better trade-off between goodness of fit and model complexity.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InformationCriteria {
+    /// The number of parameters in the model.
+    pub parameters: usize,
+    /// The residual sum of squares of the model's predicted throughput.
+    pub rss: f64,
+    /// The Akaike information criterion, AIC.
+    pub aic: f64,
+    /// The Akaike information criterion corrected for small sample sizes, AICc.
+    pub aicc: f64,
+    /// The Bayesian information criterion, BIC.
+    pub bic: f64,
+}
+
+/// Score each of the given models against the measurements using information criteria, assuming
+/// normally distributed throughput residuals.
+///
+/// This allows a full USL model to be compared with simpler nested models, e.g. those built with
+/// [Model::build_amdahl](crate::Model::build_amdahl) or
+/// [Model::build_linear](crate::Model::build_linear), to find the simplest model which explains
+/// the data.
+///
+/// A [Model]'s parameters are counted by which of its coefficients are non-zero. If a full fit may
+/// have left a coefficient at exactly zero, e.g. via
+/// [Model::build_constrained](crate::Model::build_constrained), score the model and
+/// [FitReport](crate::FitReport) from
+/// [Model::build_with_report](crate::Model::build_with_report) to count all three.
+///
+/// ```
+/// use usl::{Model, Measurement};
+/// use usl::compare::information_criteria;
+///
+/// let measurements: Vec<Measurement> =
+///     [(1, 65.0), (18, 996.0), (36, 1652.0), (72, 1853.0), (108, 1829.0), (144, 1775.0)]
+///         .iter()
+///         .map(|&m| m.into())
+///         .collect();
+/// let full = Model::build(&measurements);
+/// let amdahl = Model::build_amdahl(&measurements);
+/// let scores = information_criteria(&[&full, &amdahl], &measurements);
+/// println!("ΔAIC = {}", scores[1].aic - scores[0].aic);
+/// ```
+#[must_use]
+pub fn information_criteria(
+    models: &[&dyn ScalabilityModel],
+    measurements: &[Measurement],
+) -> Vec<InformationCriteria> {
+    let n = measurements.len() as f64;
+    models
+        .iter()
+        .map(|model| {
+            let k = model.parameters() as f64;
+            let rss: f64 = measurements
+                .iter()
+                .map(|m| (m.x - model.throughput_at_concurrency_f64(m.n)).powi(2))
+                .sum();
+            let ln_l = n * (rss / n).ln();
+            let aic = ln_l + 2.0 * k;
+            InformationCriteria {
+                parameters: model.parameters(),
+                rss,
+                aic,
+                aicc: aic + 2.0 * k * (k + 1.0) / (n - k - 1.0),
+                bic: ln_l + k * n.ln(),
+            }
+        })
+        .collect()
+}
+
+/// A test of whether two sets of measurements describe systems with different scalability, via
+/// [significance].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Significance {
+    /// The model fitted to the baseline measurements.
+    pub baseline: Model,
+    /// The model fitted to the candidate measurements.
+    pub candidate: Model,
+    /// The differences between the baseline and candidate models, whose relative changes give the
+    /// size of the effect.
+    pub diff: ModelDiff,
+    /// The F statistic comparing separate fits of the two sets with a single pooled fit.
+    pub f: f64,
+    /// The probability of an F statistic at least this large if both sets of measurements were
+    /// from the same system.
+    pub p_value: f64,
+}
+
+impl Significance {
+    /// Whether the candidate's maximum throughput is lower than the baseline's and the difference
+    /// between them is significant at the given level, e.g. `0.05`.
+    ///
+    /// Models without a finite peak have unbounded throughput, so a candidate without one is never
+    /// a regression, and a candidate with one is always a regression from a baseline without one.
+    #[must_use]
+    pub fn is_regression(&self, alpha: f64) -> bool {
+        let lower = match (
+            self.baseline.checked_max_concurrency(),
+            self.candidate.checked_max_concurrency(),
+        ) {
+            (_, None) => false,
+            (None, Some(_)) => true,
+            (Some(_), Some(_)) => self.diff.max_throughput.relative < 0.0,
+        };
+        self.p_value < alpha && lower
+    }
+}
+
+/// Test whether a candidate set of measurements describes a system with significantly different
+/// scalability than a baseline set, e.g. benchmarks of a release candidate and the last release.
+///
+/// Uses an F-test of whether fitting each set with its own model explains the measurements
+/// significantly better than fitting both with a single pooled model, assuming normally distributed
+/// throughput residuals of equal variance. Raw differences in the fitted parameters can't
+/// distinguish a real change from noise; the p-value can.
+///
+/// ```
+/// use usl::Measurement;
+/// use usl::compare::significance;
+///
+/// let baseline: Vec<Measurement> =
+///     [(1, 65.0), (18, 996.0), (36, 1652.0), (72, 1853.0), (108, 1829.0), (144, 1775.0)]
+///         .iter()
+///         .map(|&m| m.into())
+///         .collect();
+/// let candidate: Vec<Measurement> =
+///     [(1, 64.0), (18, 951.0), (36, 1502.0), (72, 1611.0), (108, 1538.0), (144, 1430.0)]
+///         .iter()
+///         .map(|&m| m.into())
+///         .collect();
+/// let result = significance(&baseline, &candidate);
+/// if result.is_regression(0.05) {
+///     println!("regressed by {:.0}%", -result.diff.max_throughput.relative * 100.0);
+/// }
+/// ```
+#[must_use]
+pub fn significance(baseline: &[Measurement], candidate: &[Measurement]) -> Significance {
+    let pooled: Vec<Measurement> = baseline.iter().chain(candidate).copied().collect();
+    let rss = |model: &Model, measurements: &[Measurement]| -> f64 {
+        measurements.iter().map(|m| (m.x - model.throughput_at_concurrency_f64(m.n)).powi(2)).sum()
+    };
+
+    let (baseline_model, report) = Model::build_with_report(baseline);
+    let candidate_model = Model::build(candidate);
+    let separate = rss(&baseline_model, baseline) + rss(&candidate_model, candidate);
+    let combined = rss(&Model::build(&pooled), &pooled);
+
+    // Every fit has the same free parameters, even if some of them end up at zero.
+    let k = report.free_parameters as f64;
+    let dof = pooled.len() as f64 - 2.0 * k;
+    let f = ((combined - separate) / k) / (separate / dof);
+    Significance {
+        baseline: baseline_model,
+        candidate: candidate_model,
+        diff: baseline_model.compare(&candidate_model),
+        f,
+        p_value: f_survival(f, k, dof),
+    }
+}
+
+impl Model {
+    /// Return a copy of the model rescaled to a single-user throughput of 1, so its throughput is
+    /// expressed as a speedup, `X(N)/λ`.
+    ///
+    /// Normalized models of systems of very different absolute speeds can be compared directly,
+    /// e.g. via [compare_normalized].
+    #[must_use]
+    pub const fn normalized(self) -> Model {
+        self.with_lambda(1.0)
+    }
+}
+
+/// The relative scalability of one of the models compared via [compare_normalized].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ranking {
+    /// The index of the model in the compared models.
+    pub index: usize,
+    /// The model, normalized via [Model::normalized].
+    pub normalized: Model,
+    /// The highest speedup the model achieves: its speedup at [Model::max_concurrency], or, for
+    /// models with no finite peak, the speedup it approaches, `1/σ`.
+    pub max_speedup: f64,
+    /// The model's maximum concurrency, or `None` if it has no finite peak.
+    pub max_concurrency: Option<u32>,
+}
+
+/// Rank the given models by how well they scale, regardless of their absolute speed, from the
+/// most to the least scalable.
+///
+/// Models are ranked by their maximum speedup over a single user, which depends only on σ and κ.
+///
+/// ```
+/// use usl::Model;
+/// use usl::compare::compare_normalized;
+///
+/// let fast = Model { sigma: 0.05, kappa: 0.001, lambda: 10_000.0 };
+/// let slow = Model { sigma: 0.01, kappa: 0.0001, lambda: 100.0 };
+/// let rankings = compare_normalized(&[fast, slow]);
+/// assert_eq!(rankings[0].index, 1);
+/// ```
+#[must_use]
+pub fn compare_normalized(models: &[Model]) -> Vec<Ranking> {
+    let mut rankings: Vec<Ranking> = models
+        .iter()
+        .enumerate()
+        .map(|(index, model)| {
+            let normalized = model.normalized();
+            let max_concurrency = normalized.checked_max_concurrency();
+            let max_speedup = match max_concurrency {
+                Some(_) => normalized.max_throughput(),
+                None => 1.0 / normalized.sigma,
+            };
+            Ranking { index, normalized, max_speedup, max_concurrency }
+        })
+        .collect();
+    rankings.sort_by(|a, b| b.max_speedup.total_cmp(&a.max_speedup));
+    rankings
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn compare_nested_models() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let full = Model::build(&measurements);
+        let amdahl = Model::build_amdahl(&measurements);
+        let linear = Model::build_linear(&measurements);
+        let scores = information_criteria(&[&full, &amdahl, &linear], &measurements);
+
+        assert_eq!(scores.iter().map(|s| s.parameters).collect::<Vec<_>>(), vec![3, 2, 1]);
+        assert!(scores[0].rss < scores[1].rss && scores[1].rss < scores[2].rss);
+        assert!(scores[0].aic < scores[1].aic && scores[1].aic < scores[2].aic);
+        assert!(scores[0].bic < scores[1].bic && scores[1].bic < scores[2].bic);
+        assert!(scores[0].aicc > scores[0].aic);
+
+        // A full fit whose κ ended up at zero still fitted three parameters.
+        let (mut pegged, report) = Model::build_with_report(&measurements);
+        pegged.kappa = 0.0;
+        assert_eq!(pegged.parameters(), 2);
+        assert_eq!(information_criteria(&[&(pegged, report)], &measurements)[0].parameters, 3);
+
+        // Fractional concurrency is evaluated as-is, not truncated.
+        let exact: Vec<Measurement> = [1.5, 2.5, 3.5, 4.5, 5.5, 6.5]
+            .iter()
+            .map(|&n| {
+                let x = full.throughput_at_concurrency_f64(n);
+                Measurement { n, x, r: n / x }
+            })
+            .collect();
+        assert_relative_eq!(information_criteria(&[&full], &exact)[0].rss, 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn significance_of_regression() {
+        let baseline: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let result = significance(&baseline, &baseline);
+        assert_relative_eq!(result.p_value, 1.0, max_relative = 0.0001);
+        assert!(!result.is_regression(0.05));
+
+        let model = Model::build(&baseline);
+        let regressed = model.scaled_kappa(1.5);
+        let candidate: Vec<Measurement> = baseline
+            .iter()
+            .map(|m| {
+                let scale = regressed.throughput_at_concurrency_f64(m.n)
+                    / model.throughput_at_concurrency_f64(m.n);
+                Measurement::concurrency_and_throughput(m.n as u32, m.x * scale)
+            })
+            .collect();
+        let result = significance(&baseline, &candidate);
+        assert!(result.p_value < 0.001, "{}", result.p_value);
+        assert!(result.f > 0.0);
+        assert!(result.diff.kappa.relative > 0.4);
+        assert!(result.is_regression(0.05));
+        assert!(!significance(&candidate, &baseline).is_regression(0.05));
+
+        let mut limitless = result;
+        limitless.candidate.kappa = 0.0;
+        assert!(!limitless.is_regression(0.05));
+
+        // A coherency penalty caps the throughput of a previously limitless system.
+        let mut capped = significance(&candidate, &baseline);
+        capped.baseline.kappa = 0.0;
+        assert!(capped.is_regression(0.05));
+    }
+
+    #[test]
+    fn normalized() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        let normalized = model.normalized();
+        assert_relative_eq!(normalized.lambda, 1.0);
+        assert_relative_eq!(
+            normalized.throughput_at_concurrency(20),
+            model.throughput_at_concurrency(20) / model.lambda
+        );
+        assert_eq!(normalized.max_concurrency(), model.max_concurrency());
+
+        let amdahl = Model { sigma: 0.1, kappa: 0.0, lambda: 10.0 };
+        let scaled = model.scaled_lambda(100.0);
+        let rankings = compare_normalized(&[scaled, amdahl, model]);
+        assert_eq!(rankings.iter().map(|r| r.index).collect::<Vec<_>>(), vec![0, 2, 1]);
+        assert_relative_eq!(rankings[0].max_speedup, rankings[1].max_speedup);
+        assert_relative_eq!(rankings[1].max_speedup, model.max_throughput() / model.lambda);
+        assert_eq!(rankings[2].max_concurrency, None);
+        assert_relative_eq!(rankings[2].max_speedup, 10.0);
+    }
+}
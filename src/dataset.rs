@@ -0,0 +1,282 @@
+use std::ops::Deref;
+
+use crate::{Measurement, Spread};
+
+/// A set of measurements of a system, with utilities for inspecting and preprocessing them before
+/// building a model.
+///
+/// A dataset dereferences to a slice of measurements, so it can be passed anywhere a
+/// `&[Measurement]` is expected, e.g. [Model::build](crate::Model::build).
+///
+/// ```
+/// use usl::{Dataset, Model};
+///
+/// let dataset: Dataset =
+///     [(1, 65.0), (18, 996.0), (36, 1652.0), (72, 1853.0), (108, 1829.0), (144, 1775.0)]
+///         .iter()
+///         .map(|&m| m.into())
+///         .collect();
+/// let stats = dataset.statistics();
+/// println!("{} measurements at {} concurrency levels", stats.len, stats.levels);
+/// let model = Model::build(&dataset);
+/// ```
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dataset {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    metadata: Metadata,
+    measurements: Vec<Measurement>,
+}
+
+/// Optional information describing where a [Dataset]'s measurements came from.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Metadata {
+    /// The name of the measured system.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub system: Option<String>,
+    /// An identifier for the run which produced the measurements.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub run_id: Option<String>,
+}
+
+/// Summary statistics of the measurements at a single concurrency level of a [Dataset], via
+/// [Dataset::group_by_concurrency].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Level {
+    /// The concurrency level.
+    pub concurrency: f64,
+    /// The number of measurements at the concurrency level.
+    pub count: usize,
+    /// The spread of the measurements' throughput, in events/sec.
+    pub throughput: Spread,
+    /// The spread of the measurements' latency, in seconds.
+    pub latency: Spread,
+}
+
+/// Summary statistics of a [Dataset], via [Dataset::statistics].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Statistics {
+    /// The number of measurements.
+    pub len: usize,
+    /// The number of distinct concurrency levels.
+    pub levels: usize,
+    /// The spread of the measurements' concurrency.
+    pub concurrency: Spread,
+    /// The spread of the measurements' throughput, in events/sec.
+    pub throughput: Spread,
+    /// The spread of the measurements' latency, in seconds.
+    pub latency: Spread,
+}
+
+impl Dataset {
+    /// Create a dataset of the given measurements.
+    #[must_use]
+    pub const fn new(measurements: Vec<Measurement>) -> Dataset {
+        Dataset { metadata: Metadata { system: None, run_id: None }, measurements }
+    }
+
+    /// Return the dataset with the given metadata.
+    #[must_use]
+    pub fn with_metadata(self, metadata: Metadata) -> Dataset {
+        Dataset { metadata, ..self }
+    }
+
+    /// The metadata describing the dataset.
+    #[must_use]
+    pub const fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// The measurements in the dataset.
+    #[must_use]
+    pub fn measurements(&self) -> &[Measurement] {
+        &self.measurements
+    }
+
+    /// Consume the dataset, returning its measurements.
+    #[must_use]
+    pub fn into_measurements(self) -> Vec<Measurement> {
+        self.measurements
+    }
+
+    /// Add a measurement to the dataset.
+    pub fn push(&mut self, measurement: Measurement) {
+        self.measurements.push(measurement);
+    }
+
+    /// Sort the measurements in order of concurrency. The sort is stable, so measurements at the
+    /// same concurrency keep their relative order.
+    pub fn sort_by_concurrency(&mut self) {
+        self.measurements.sort_by(|a, b| a.n.total_cmp(&b.n));
+    }
+
+    /// Group the measurements by concurrency, returning a dataset for each distinct concurrency
+    /// level, in order of concurrency. Each dataset has the same metadata as this one.
+    #[must_use]
+    pub fn by_concurrency(&self) -> Vec<Dataset> {
+        let mut sorted = self.clone();
+        sorted.sort_by_concurrency();
+        let mut groups: Vec<Dataset> = Vec::new();
+        for m in sorted.measurements {
+            match groups.last_mut() {
+                Some(group) if group.measurements[0].n == m.n => group.push(m),
+                _ => groups.push(Dataset::new(vec![m]).with_metadata(self.metadata.clone())),
+            }
+        }
+        groups
+    }
+
+    /// Calculate summary statistics of the measurements at each distinct concurrency level, in
+    /// order of concurrency.
+    ///
+    /// Levels with a high [Spread::coefficient_of_variation] are noisy, and benefit from more
+    /// repetitions or from being down-weighted when fitting (e.g. via
+    /// [Model::build_weighted](crate::Model::build_weighted)).
+    #[must_use]
+    pub fn group_by_concurrency(&self) -> Vec<Level> {
+        self.by_concurrency()
+            .iter()
+            .map(|group| {
+                let spread = |f: fn(&Measurement) -> f64| {
+                    Spread::of(&group.iter().map(f).collect::<Vec<f64>>())
+                };
+                Level {
+                    concurrency: group[0].n,
+                    count: group.len(),
+                    throughput: spread(|m| m.x),
+                    latency: spread(|m| m.r),
+                }
+            })
+            .collect()
+    }
+
+    /// Calculate summary statistics of the measurements.
+    ///
+    /// Panics if the dataset is empty.
+    #[must_use]
+    pub fn statistics(&self) -> Statistics {
+        assert!(!self.is_empty(), "no measurements");
+        let spread = |f: fn(&Measurement) -> f64| {
+            Spread::of(&self.measurements.iter().map(f).collect::<Vec<f64>>())
+        };
+        Statistics {
+            len: self.len(),
+            levels: self.by_concurrency().len(),
+            concurrency: spread(|m| m.n),
+            throughput: spread(|m| m.x),
+            latency: spread(|m| m.r),
+        }
+    }
+}
+
+impl Deref for Dataset {
+    type Target = [Measurement];
+
+    fn deref(&self) -> &Self::Target {
+        &self.measurements
+    }
+}
+
+impl AsRef<[Measurement]> for Dataset {
+    fn as_ref(&self) -> &[Measurement] {
+        &self.measurements
+    }
+}
+
+impl From<Vec<Measurement>> for Dataset {
+    fn from(measurements: Vec<Measurement>) -> Self {
+        Dataset::new(measurements)
+    }
+}
+
+impl FromIterator<Measurement> for Dataset {
+    fn from_iter<T: IntoIterator<Item = Measurement>>(iter: T) -> Self {
+        Dataset::new(iter.into_iter().collect())
+    }
+}
+
+impl Extend<Measurement> for Dataset {
+    fn extend<T: IntoIterator<Item = Measurement>>(&mut self, iter: T) {
+        self.measurements.extend(iter);
+    }
+}
+
+impl IntoIterator for Dataset {
+    type Item = Measurement;
+    type IntoIter = std::vec::IntoIter<Measurement>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.measurements.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Dataset {
+    type Item = &'a Measurement;
+    type IntoIter = std::slice::Iter<'a, Measurement>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.measurements.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+    use crate::Model;
+
+    #[test]
+    fn dataset() {
+        let mut dataset: Dataset = MEASUREMENTS.iter().rev().map(|&m| m.into()).collect();
+        assert_eq!(dataset.len(), 32);
+        assert_eq!(dataset[0].n, 32.0);
+
+        dataset.sort_by_concurrency();
+        assert_eq!(
+            dataset.iter().map(|m| m.n).collect::<Vec<f64>>(),
+            (1..=32).map(f64::from).collect::<Vec<f64>>()
+        );
+
+        let expected: Model = MEASUREMENTS.iter().collect();
+        assert_relative_eq!(Model::build(&dataset).kappa, expected.kappa, max_relative = 1e-6);
+
+        dataset.push(Measurement::concurrency_and_throughput(4, 3000.0));
+        let groups = dataset.by_concurrency();
+        assert_eq!(groups.len(), 32);
+        assert_eq!(groups[3].len(), 2);
+        assert!(groups[3].iter().all(|m| m.n == 4.0));
+
+        let stats = dataset.statistics();
+        assert_eq!(stats.len, 33);
+        assert_eq!(stats.levels, 32);
+        assert_eq!((stats.concurrency.min, stats.concurrency.max), (1.0, 32.0));
+        assert_relative_eq!(stats.throughput.mean, dataset.iter().map(|m| m.x).sum::<f64>() / 33.0);
+        assert_eq!(dataset.clone().into_measurements().len(), 33);
+        assert_eq!((&dataset).into_iter().count(), 33);
+    }
+
+    #[test]
+    fn group_by_concurrency() {
+        let dataset: Dataset = [(2, 100.0), (1, 60.0), (2, 120.0), (1, 40.0), (2, 110.0)]
+            .iter()
+            .map(|&m| Measurement::from(m))
+            .collect();
+        let levels = dataset.group_by_concurrency();
+        assert_eq!(levels.len(), 2);
+
+        assert_eq!((levels[0].concurrency, levels[0].count), (1.0, 2));
+        assert_relative_eq!(levels[0].throughput.mean, 50.0);
+        assert_relative_eq!(levels[0].throughput.std_dev, 200f64.sqrt());
+        assert_relative_eq!(levels[0].throughput.coefficient_of_variation(), 200f64.sqrt() / 50.0);
+        assert_relative_eq!(levels[0].latency.mean, (1.0 / 60.0 + 1.0 / 40.0) / 2.0);
+
+        assert_eq!((levels[1].concurrency, levels[1].count), (2.0, 3));
+        assert_relative_eq!(levels[1].throughput.mean, 110.0);
+        assert_relative_eq!(levels[1].throughput.std_dev, 10.0);
+    }
+}
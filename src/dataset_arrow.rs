@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow_array::{Array, ArrayRef, Float64Array, RecordBatch};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+
+use crate::{Dataset, Measurement, Metadata};
+
+const SYSTEM: &str = "system";
+const RUN_ID: &str = "run_id";
+
+impl Dataset {
+    /// Read a dataset from an Arrow record batch.
+    ///
+    /// The batch must have non-null `Float64` columns named `n`, `x`, and `r`, holding each
+    /// measurement's concurrency, throughput in events/sec, and latency in seconds. Any other
+    /// columns are ignored. The `system` and `run_id` keys of the schema's metadata, if present,
+    /// are read into the dataset's [Metadata].
+    ///
+    /// ```
+    /// use usl::Dataset;
+    ///
+    /// let dataset: Dataset = [(1, 955.16), (2, 1878.91), (3, 2688.01)]
+    ///     .iter()
+    ///     .map(|&m| m.into())
+    ///     .collect();
+    /// let batch = dataset.to_record_batch();
+    /// assert_eq!(Dataset::from_record_batch(&batch).unwrap().len(), 3);
+    /// ```
+    pub fn from_record_batch(batch: &RecordBatch) -> Result<Dataset, ArrowError> {
+        let column = |name: &str| {
+            let column = batch
+                .column_by_name(name)
+                .ok_or_else(|| ArrowError::SchemaError(format!("missing column {:?}", name)))?;
+            let values = column.as_any().downcast_ref::<Float64Array>().ok_or_else(|| {
+                ArrowError::SchemaError(format!(
+                    "column {:?} has type {}, expected {}",
+                    name,
+                    column.data_type(),
+                    DataType::Float64
+                ))
+            })?;
+            if values.null_count() > 0 {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "column {:?} contains nulls",
+                    name
+                )));
+            }
+            Ok(values.values())
+        };
+        let (n, x, r) = (column("n")?, column("x")?, column("r")?);
+
+        let schema = batch.schema();
+        let metadata = Metadata {
+            system: schema.metadata().get(SYSTEM).cloned(),
+            run_id: schema.metadata().get(RUN_ID).cloned(),
+        };
+        let measurements = n
+            .iter()
+            .zip(x.iter())
+            .zip(r.iter())
+            .map(|((&n, &x), &r)| Measurement { n, x, r })
+            .collect();
+        Ok(Dataset::new(measurements).with_metadata(metadata))
+    }
+
+    /// Write the dataset as an Arrow record batch, in the format read by
+    /// [Dataset::from_record_batch].
+    #[must_use]
+    pub fn to_record_batch(&self) -> RecordBatch {
+        let metadata = self.metadata();
+        let metadata: HashMap<String, String> =
+            [(SYSTEM, &metadata.system), (RUN_ID, &metadata.run_id)]
+                .into_iter()
+                .filter_map(|(k, v)| Some((k.to_string(), v.clone()?)))
+                .collect();
+        let schema = Schema::new(vec![
+            Field::new("n", DataType::Float64, false),
+            Field::new("x", DataType::Float64, false),
+            Field::new("r", DataType::Float64, false),
+        ])
+        .with_metadata(metadata);
+        let column = |f: fn(&Measurement) -> f64| -> ArrayRef {
+            Arc::new(self.iter().map(f).collect::<Float64Array>())
+        };
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![column(|m| m.n), column(|m| m.x), column(|m| m.r)],
+        )
+        .expect("columns match the schema")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::Int32Array;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn record_batch_round_trip() {
+        let metadata = Metadata { system: Some("db-primary".into()), run_id: None };
+        let dataset = MEASUREMENTS
+            .iter()
+            .map(|&m| m.into())
+            .collect::<Dataset>()
+            .with_metadata(metadata.clone());
+
+        let batch = dataset.to_record_batch();
+        assert_eq!(batch.num_rows(), 32);
+        let decoded = Dataset::from_record_batch(&batch).unwrap();
+        assert_eq!(decoded.metadata(), &metadata);
+        for (a, b) in decoded.iter().zip(dataset.iter()) {
+            assert_eq!((a.n, a.x, a.r), (b.n, b.x, b.r));
+        }
+
+        let schema = Schema::new(vec![
+            Field::new("n", DataType::Int32, false),
+            Field::new("x", DataType::Float64, false),
+            Field::new("r", DataType::Float64, false),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1])),
+                Arc::new(Float64Array::from(vec![1.0])),
+                Arc::new(Float64Array::from(vec![1.0])),
+            ],
+        )
+        .unwrap();
+        assert!(matches!(Dataset::from_record_batch(&batch), Err(ArrowError::SchemaError(_))));
+    }
+}
@@ -0,0 +1,201 @@
+use std::error::Error;
+use std::fmt;
+use std::io::Read;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::{Dataset, Measurement};
+
+/// Which two quantities the columns of a CSV file contain, for [CsvOptions].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum MeasurementKind {
+    /// Concurrency (an integer) and throughput (in events/sec).
+    #[default]
+    ConcurrencyAndThroughput,
+    /// Concurrency (an integer) and mean latency (in seconds).
+    ConcurrencyAndLatency,
+    /// Throughput (in events/sec) and mean latency (in seconds).
+    ThroughputAndLatency,
+}
+
+/// A column of a CSV file, for [CsvOptions].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Column {
+    /// The column at the given zero-based index.
+    Index(usize),
+    /// The column with the given header.
+    Name(String),
+}
+
+/// Options for reading a [Dataset] from a CSV file, via [Dataset::from_csv_reader].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// The quantities the two columns contain.
+    pub kind: MeasurementKind,
+    /// The columns containing the first and second quantities, in the order given by
+    /// [CsvOptions::kind].
+    pub columns: (Column, Column),
+    /// Whether the first row of the file is a header. Columns can only be named if it is.
+    pub has_headers: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            kind: MeasurementKind::default(),
+            columns: (Column::Index(0), Column::Index(1)),
+            has_headers: true,
+        }
+    }
+}
+
+/// An error returned when a [Dataset] can't be read from a CSV file.
+#[derive(Debug)]
+pub enum CsvError {
+    /// The file couldn't be read or parsed as CSV.
+    Csv(csv::Error),
+    /// The file has no column with the given name, or has no headers.
+    MissingColumn(String),
+    /// A field couldn't be parsed as a number.
+    InvalidField {
+        /// The one-based line number of the record.
+        line: u64,
+        /// The field's value.
+        value: String,
+    },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvError::Csv(err) => write!(f, "csv error: {}", err),
+            CsvError::MissingColumn(name) => write!(f, "missing column {:?}", name),
+            CsvError::InvalidField { line, value } => {
+                write!(f, "invalid field {:?} on line {}", value, line)
+            }
+        }
+    }
+}
+
+impl Error for CsvError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CsvError::Csv(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<csv::Error> for CsvError {
+    fn from(err: csv::Error) -> Self {
+        CsvError::Csv(err)
+    }
+}
+
+impl Dataset {
+    /// Read a dataset from the CSV file at the given path.
+    ///
+    /// See [Dataset::from_csv_reader].
+    pub fn from_csv_path(
+        path: impl AsRef<Path>,
+        options: &CsvOptions,
+    ) -> Result<Dataset, CsvError> {
+        let reader = csv::ReaderBuilder::new().has_headers(options.has_headers).from_path(path)?;
+        read(reader, options)
+    }
+
+    /// Read a dataset from CSV data, with one measurement per record.
+    ///
+    /// ```
+    /// use usl::{Column, CsvOptions, Dataset, MeasurementKind};
+    ///
+    /// let csv = "threads,p50,mean\n1,0.0101,0.0102\n2,0.0105,0.0107\n";
+    /// let options = CsvOptions {
+    ///     kind: MeasurementKind::ConcurrencyAndLatency,
+    ///     columns: (Column::Name("threads".into()), Column::Name("mean".into())),
+    ///     has_headers: true,
+    /// };
+    /// let dataset = Dataset::from_csv_reader(csv.as_bytes(), &options).unwrap();
+    /// assert_eq!(dataset.len(), 2);
+    /// ```
+    pub fn from_csv_reader(reader: impl Read, options: &CsvOptions) -> Result<Dataset, CsvError> {
+        let reader = csv::ReaderBuilder::new().has_headers(options.has_headers).from_reader(reader);
+        read(reader, options)
+    }
+}
+
+fn read<R: Read>(mut reader: csv::Reader<R>, options: &CsvOptions) -> Result<Dataset, CsvError> {
+    let mut index = |column: &Column| match column {
+        Column::Index(i) => Ok(*i),
+        Column::Name(name) if options.has_headers => reader
+            .headers()?
+            .iter()
+            .position(|h| h == name)
+            .ok_or_else(|| CsvError::MissingColumn(name.clone())),
+        Column::Name(name) => Err(CsvError::MissingColumn(name.clone())),
+    };
+    let (a, b) = (index(&options.columns.0)?, index(&options.columns.1)?);
+
+    let mut dataset = Dataset::default();
+    for record in reader.records() {
+        let record = record?;
+        let line = record.position().map_or(0, |p| p.line());
+        let field = |i: usize| record.get(i).unwrap_or_default();
+        let invalid = |i: usize| CsvError::InvalidField { line, value: field(i).to_string() };
+        let int = |i: usize| field(i).trim().parse::<u32>().map_err(|_| invalid(i));
+        let float = |i: usize| field(i).trim().parse::<f64>().map_err(|_| invalid(i));
+        let duration = |i: usize| {
+            float(i).and_then(|r| Duration::try_from_secs_f64(r).map_err(|_| invalid(i)))
+        };
+        dataset.push(match options.kind {
+            MeasurementKind::ConcurrencyAndThroughput => {
+                Measurement::concurrency_and_throughput(int(a)?, float(b)?)
+            }
+            MeasurementKind::ConcurrencyAndLatency => {
+                Measurement::concurrency_and_latency(int(a)?, duration(b)?)
+            }
+            MeasurementKind::ThroughputAndLatency => {
+                Measurement::throughput_and_latency(float(a)?, duration(b)?)
+            }
+        });
+    }
+    Ok(dataset)
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn from_csv_reader() {
+        let csv = "n,x\n1,65\n18,996\n36,1652\n";
+        let dataset = Dataset::from_csv_reader(csv.as_bytes(), &CsvOptions::default()).unwrap();
+        assert_eq!(dataset.len(), 3);
+        assert_eq!((dataset[1].n, dataset[1].x), (18.0, 996.0));
+
+        let csv = "0.5,100\n0.25,200\n";
+        let options = CsvOptions {
+            kind: MeasurementKind::ThroughputAndLatency,
+            columns: (Column::Index(1), Column::Index(0)),
+            has_headers: false,
+        };
+        let dataset = Dataset::from_csv_reader(csv.as_bytes(), &options).unwrap();
+        assert_relative_eq!(dataset[0].n, 50.0);
+        assert_relative_eq!(dataset[1].r, 0.25);
+
+        let options = CsvOptions {
+            columns: (Column::Name("n".into()), Column::Name("y".into())),
+            ..CsvOptions::default()
+        };
+        assert!(matches!(
+            Dataset::from_csv_reader("n,x\n1,65\n".as_bytes(), &options),
+            Err(CsvError::MissingColumn(name)) if name == "y"
+        ));
+        assert!(matches!(
+            Dataset::from_csv_reader("n,x\n1,65\n2.5,100\n".as_bytes(), &CsvOptions::default()),
+            Err(CsvError::InvalidField { line: 3, value }) if value == "2.5"
+        ));
+    }
+}
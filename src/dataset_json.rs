@@ -0,0 +1,229 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Read, Write};
+
+use crate::{Dataset, Measurement};
+
+/// An iterator over measurements read from newline-delimited JSON, via [JsonLines::new].
+///
+/// Each non-blank line is a JSON object with a measurement's concurrency (`n`), throughput in
+/// events/sec (`x`), and latency in seconds (`r`):
+///
+/// ```json
+/// { "n": 1.0, "x": 955.16, "r": 0.00104695 }
+/// { "n": 2.0, "x": 1878.91, "r": 0.00106445 }
+/// ```
+///
+/// A truncated final line, e.g. one a load generator is still writing, is held back rather than
+/// reported as an error. The iterator then returns `None`, but calling [Iterator::next] again once
+/// more data has been appended resumes reading where it left off.
+#[derive(Debug)]
+pub struct JsonLines<R> {
+    reader: R,
+    line: u64,
+    buf: String,
+}
+
+/// An error returned when a measurement can't be read from newline-delimited JSON.
+#[derive(Debug)]
+pub enum JsonLinesError {
+    /// The underlying reader returned an error.
+    Io(io::Error),
+    /// A line couldn't be parsed as a measurement.
+    InvalidLine {
+        /// The one-based line number.
+        line: u64,
+        /// The parse error.
+        error: serde_json::Error,
+    },
+}
+
+impl fmt::Display for JsonLinesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonLinesError::Io(err) => write!(f, "io error: {}", err),
+            JsonLinesError::InvalidLine { line, error } => {
+                write!(f, "invalid measurement on line {}: {}", line, error)
+            }
+        }
+    }
+}
+
+impl Error for JsonLinesError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            JsonLinesError::Io(err) => Some(err),
+            JsonLinesError::InvalidLine { error, .. } => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for JsonLinesError {
+    fn from(err: io::Error) -> Self {
+        JsonLinesError::Io(err)
+    }
+}
+
+impl<R: BufRead> JsonLines<R> {
+    /// Read measurements from the given reader, one per line.
+    pub const fn new(reader: R) -> JsonLines<R> {
+        JsonLines { reader, line: 0, buf: String::new() }
+    }
+
+    /// Return a mutable reference to the underlying reader.
+    pub const fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+}
+
+impl<R: BufRead> Iterator for JsonLines<R> {
+    type Item = Result<Measurement, JsonLinesError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Err(err) = self.reader.read_line(&mut self.buf) {
+                return Some(Err(err.into()));
+            }
+            if self.buf.is_empty() {
+                return None;
+            }
+
+            let complete = self.buf.ends_with('\n');
+            let result = serde_json::from_str::<Measurement>(&self.buf);
+            if !complete && matches!(&result, Err(err) if err.is_eof()) {
+                // Hold back a truncated final line until the rest of it is appended.
+                return None;
+            }
+
+            self.line += 1;
+            let blank = self.buf.trim().is_empty();
+            self.buf.clear();
+            if !blank {
+                let line = self.line;
+                return Some(result.map_err(|error| JsonLinesError::InvalidLine { line, error }));
+            }
+        }
+    }
+}
+
+impl Dataset {
+    /// Read a dataset from a JSON document.
+    ///
+    /// The document is an object with a `measurements` array, each element of which is an object
+    /// with the measurement's concurrency (`n`), throughput in events/sec (`x`), and latency in
+    /// seconds (`r`). The object may also have `system` and `run_id` strings, which are read into
+    /// the dataset's [Metadata](crate::Metadata):
+    ///
+    /// ```json
+    /// {
+    ///   "system": "db-primary",
+    ///   "run_id": "2023-02-01T12:00:00Z",
+    ///   "measurements": [
+    ///     { "n": 1.0, "x": 955.16, "r": 0.00104695 },
+    ///     { "n": 2.0, "x": 1878.91, "r": 0.00106445 }
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// ```
+    /// use usl::Dataset;
+    ///
+    /// let json = r#"{"system": "db-primary", "measurements": [{"n": 1, "x": 955.16, "r": 0.00104695}]}"#;
+    /// let dataset = Dataset::from_json(json).unwrap();
+    /// assert_eq!(dataset.len(), 1);
+    /// assert_eq!(dataset.metadata().system.as_deref(), Some("db-primary"));
+    /// ```
+    pub fn from_json(json: &str) -> serde_json::Result<Dataset> {
+        serde_json::from_str(json)
+    }
+
+    /// Read a dataset from a JSON document, as with [Dataset::from_json].
+    pub fn from_json_reader(reader: impl Read) -> serde_json::Result<Dataset> {
+        serde_json::from_reader(reader)
+    }
+
+    /// Read a dataset from newline-delimited JSON, with one measurement per line.
+    ///
+    /// See [JsonLines] for the format.
+    ///
+    /// ```
+    /// use usl::Dataset;
+    ///
+    /// let jsonl = "{\"n\": 1, \"x\": 955.16, \"r\": 0.00104695}\n{\"n\": 2, \"x\": 1878.91, \"r\": 0.00106445}\n";
+    /// let dataset = Dataset::from_json_lines(jsonl.as_bytes()).unwrap();
+    /// assert_eq!(dataset.len(), 2);
+    /// ```
+    pub fn from_json_lines(reader: impl BufRead) -> Result<Dataset, JsonLinesError> {
+        JsonLines::new(reader).collect()
+    }
+
+    /// Write the dataset as a JSON document, in the format read by [Dataset::from_json].
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("datasets are always serializable")
+    }
+
+    /// Write the dataset as a JSON document to the given writer, in the format read by
+    /// [Dataset::from_json].
+    pub fn to_json_writer(&self, writer: impl Write) -> serde_json::Result<()> {
+        serde_json::to_writer(writer, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+    use crate::Metadata;
+
+    #[test]
+    fn json_round_trip() {
+        let metadata =
+            Metadata { system: Some("db-primary".into()), run_id: Some("run-42".into()) };
+        let dataset = MEASUREMENTS
+            .iter()
+            .map(|&m| m.into())
+            .collect::<Dataset>()
+            .with_metadata(metadata.clone());
+
+        let json = dataset.to_json();
+        let decoded = Dataset::from_json(&json).unwrap();
+        assert_eq!(decoded.metadata(), &metadata);
+        assert_eq!(decoded.len(), dataset.len());
+        for (a, b) in decoded.iter().zip(dataset.iter()) {
+            assert_eq!((a.n, a.x), (b.n, b.x));
+        }
+
+        let mut buf = Vec::new();
+        Dataset::new(vec![dataset[0]]).to_json_writer(&mut buf).unwrap();
+        let decoded = Dataset::from_json_reader(buf.as_slice()).unwrap();
+        assert_eq!(decoded.metadata(), &Metadata::default());
+        assert_eq!(decoded.len(), 1);
+        assert!(!String::from_utf8(buf).unwrap().contains("system"));
+
+        assert!(Dataset::from_json(r#"{"measurements": [{"n": 1.0, "x": 2.0}]}"#).is_err());
+    }
+
+    #[test]
+    fn json_lines() {
+        let mut data = Vec::new();
+        for &(n, x) in &MEASUREMENTS[..3] {
+            let m: Measurement = (n, x).into();
+            data.extend_from_slice(serde_json::to_string(&m).unwrap().as_bytes());
+            data.extend_from_slice(b"\n\n");
+        }
+        data.extend_from_slice(br#"{"n": 4.0, "x": 3"#);
+
+        let mut lines = JsonLines::new(io::Cursor::new(data));
+        assert_eq!(lines.by_ref().map(|m| m.unwrap().n).collect::<Vec<f64>>(), vec![1.0, 2.0, 3.0]);
+
+        // Append the rest of the truncated line, plus an invalid one.
+        lines.get_mut().get_mut().extend_from_slice(b"548.68, \"r\": 0.001127}\n{\"n\": true}");
+        assert_eq!(lines.next().unwrap().unwrap().n, 4.0);
+        assert!(matches!(lines.next(), Some(Err(JsonLinesError::InvalidLine { line: 8, .. }))));
+        assert!(lines.next().is_none());
+
+        let dataset = Dataset::from_json_lines(&b"{\"n\": 1, \"x\": 2, \"r\": 0.5}"[..]).unwrap();
+        assert_eq!(dataset.len(), 1);
+    }
+}
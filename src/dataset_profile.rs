@@ -0,0 +1,156 @@
+use crate::Dataset;
+
+/// A gap between two adjacent concurrency levels of a [Dataset], via [Dataset::profile].
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gap {
+    /// The lower concurrency level.
+    pub from: f64,
+    /// The higher concurrency level.
+    pub to: f64,
+}
+
+impl Gap {
+    /// The difference between the two concurrency levels.
+    #[must_use]
+    pub fn width(&self) -> f64 {
+        self.to - self.from
+    }
+}
+
+/// A concurrency level at which the mean throughput dips below that of the level before it, only
+/// to recover at a higher level, via [Dataset::profile].
+///
+/// The Universal Scalability Law allows throughput to fall as concurrency increases, but not to
+/// rise again afterwards, so dips like these usually indicate a noisy or misconfigured benchmark.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonotonicityBreak {
+    /// The concurrency level at which throughput dips.
+    pub concurrency: f64,
+    /// The mean throughput at the level, in events/sec.
+    pub throughput: f64,
+    /// The mean throughput at the level before it, in events/sec.
+    pub previous_throughput: f64,
+}
+
+/// A description of how well a [Dataset] covers the concurrency levels of the measured system,
+/// via [Dataset::profile].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DatasetProfile {
+    /// The lowest and highest concurrency levels.
+    pub range: (f64, f64),
+    /// The number of distinct concurrency levels.
+    pub levels: usize,
+    /// The gaps between adjacent concurrency levels, widest first.
+    pub gaps: Vec<Gap>,
+    /// Each concurrency level and the number of measurements at it, in order of concurrency.
+    pub replicates: Vec<(f64, usize)>,
+    /// The concurrency levels at which throughput dips and later recovers, in order of
+    /// concurrency.
+    pub monotonicity_breaks: Vec<MonotonicityBreak>,
+}
+
+impl DatasetProfile {
+    /// The fewest measurements at any concurrency level.
+    #[must_use]
+    pub fn min_replicates(&self) -> usize {
+        self.replicates.iter().map(|&(_, count)| count).min().unwrap_or(0)
+    }
+}
+
+impl Dataset {
+    /// Describe how well the dataset covers the concurrency levels of the measured system.
+    ///
+    /// Poor coverage, e.g. a narrow range of concurrency, wide gaps between levels, or levels
+    /// measured only once, is the most common cause of poorly fitted models, so it's worth checking
+    /// before fitting.
+    ///
+    /// ```
+    /// use usl::Dataset;
+    ///
+    /// let dataset: Dataset =
+    ///     [(1, 65.0), (2, 130.0), (4, 250.0), (8, 240.0), (16, 700.0), (64, 1700.0)]
+    ///         .iter()
+    ///         .map(|&m| m.into())
+    ///         .collect();
+    /// let profile = dataset.profile();
+    /// assert_eq!(profile.range, (1.0, 64.0));
+    /// assert_eq!((profile.gaps[0].from, profile.gaps[0].to), (16.0, 64.0));
+    /// assert_eq!(profile.monotonicity_breaks[0].concurrency, 8.0);
+    /// ```
+    ///
+    /// Panics if the dataset is empty.
+    #[must_use]
+    pub fn profile(&self) -> DatasetProfile {
+        assert!(!self.is_empty(), "no measurements");
+        let levels = self.group_by_concurrency();
+
+        let mut gaps: Vec<Gap> = levels
+            .windows(2)
+            .map(|w| Gap { from: w[0].concurrency, to: w[1].concurrency })
+            .collect();
+        gaps.sort_by(|a, b| b.width().total_cmp(&a.width()));
+
+        // A dip is only suspicious if some later level's throughput exceeds the level before it.
+        let monotonicity_breaks = levels
+            .windows(2)
+            .enumerate()
+            .filter(|(i, w)| {
+                let previous = w[0].throughput.mean;
+                w[1].throughput.mean < previous
+                    && levels[i + 2..].iter().any(|l| l.throughput.mean > previous)
+            })
+            .map(|(_, w)| MonotonicityBreak {
+                concurrency: w[1].concurrency,
+                throughput: w[1].throughput.mean,
+                previous_throughput: w[0].throughput.mean,
+            })
+            .collect();
+
+        DatasetProfile {
+            range: (levels[0].concurrency, levels[levels.len() - 1].concurrency),
+            levels: levels.len(),
+            gaps,
+            replicates: levels.iter().map(|l| (l.concurrency, l.count)).collect(),
+            monotonicity_breaks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+    use crate::Measurement;
+
+    #[test]
+    fn profile() {
+        let mut dataset: Dataset = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let profile = dataset.profile();
+        assert_eq!(profile.range, (1.0, 32.0));
+        assert_eq!(profile.levels, 32);
+        assert_eq!(profile.gaps.len(), 31);
+        assert!(profile.gaps.iter().all(|g| g.width() == 1.0));
+        assert_eq!(profile.min_replicates(), 1);
+
+        // Throughput in the test data dips at 25 before recovering. Its later dips, from 28, never
+        // recover to the throughput of the level before them.
+        assert_eq!(
+            profile.monotonicity_breaks,
+            vec![MonotonicityBreak {
+                concurrency: 25.0,
+                throughput: 12075.41,
+                previous_throughput: 12089.37
+            }]
+        );
+
+        dataset.push(Measurement::concurrency_and_throughput(64, 11_000.0));
+        dataset.push(Measurement::concurrency_and_throughput(1, 960.0));
+        let profile = dataset.profile();
+        assert_eq!(profile.gaps[0], Gap { from: 32.0, to: 64.0 });
+        assert_eq!(profile.replicates[0], (1.0, 2));
+        assert_eq!(profile.replicates[32], (64.0, 1));
+    }
+}
@@ -0,0 +1,106 @@
+use crate::Model;
+
+/// The change in a quantity between two models.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Change {
+    /// The value for the baseline model.
+    pub before: f64,
+    /// The value for the compared model.
+    pub after: f64,
+    /// The relative change, `(after - before)/before`.
+    pub relative: f64,
+}
+
+impl Change {
+    pub(crate) fn new(before: f64, after: f64) -> Change {
+        Change { before, after, relative: (after - before) / before }
+    }
+}
+
+/// An overall assessment of how a system's scalability has changed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Verdict {
+    /// The maximum throughput increased by more than the tolerance.
+    Improved,
+    /// The maximum throughput decreased by more than the tolerance.
+    Regressed,
+    /// The maximum throughput changed by no more than the tolerance.
+    Unchanged,
+}
+
+/// A summary of the differences between two models, via [Model::compare].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModelDiff {
+    /// The change in σ.
+    pub sigma: Change,
+    /// The change in κ.
+    pub kappa: Change,
+    /// The change in λ.
+    pub lambda: Change,
+    /// The change in [Model::max_throughput].
+    pub max_throughput: Change,
+    /// The change in [Model::max_concurrency]. Models without a finite peak have an infinite
+    /// maximum concurrency.
+    pub max_concurrency: Change,
+}
+
+impl ModelDiff {
+    /// Assess whether the compared model is an improvement on the baseline, based on the relative
+    /// change in maximum throughput. Changes within `tolerance` (e.g. `0.05` for 5%) are
+    /// considered noise.
+    #[must_use]
+    pub fn verdict(&self, tolerance: f64) -> Verdict {
+        if self.max_throughput.relative > tolerance {
+            Verdict::Improved
+        } else if self.max_throughput.relative < -tolerance {
+            Verdict::Regressed
+        } else {
+            Verdict::Unchanged
+        }
+    }
+}
+
+impl Model {
+    /// Summarize the differences between this model, as a baseline, and another, e.g. models
+    /// fitted to benchmarks of the last and the current release.
+    #[must_use]
+    pub fn compare(&self, other: &Model) -> ModelDiff {
+        let max_concurrency =
+            |m: &Model| m.checked_max_concurrency().map_or(f64::INFINITY, f64::from);
+        ModelDiff {
+            sigma: Change::new(self.sigma, other.sigma),
+            kappa: Change::new(self.kappa, other.kappa),
+            lambda: Change::new(self.lambda, other.lambda),
+            max_throughput: Change::new(self.max_throughput(), other.max_throughput()),
+            max_concurrency: Change::new(max_concurrency(self), max_concurrency(other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn compare() {
+        let baseline: Model = MEASUREMENTS.iter().collect();
+        let diff = baseline.compare(&baseline.scaled_lambda(1.1));
+        assert_relative_eq!(diff.sigma.relative, 0.0);
+        assert_relative_eq!(diff.lambda.before, baseline.lambda);
+        assert_relative_eq!(diff.lambda.relative, 0.1, max_relative = 1e-9);
+        assert_relative_eq!(diff.max_throughput.relative, 0.1, max_relative = 1e-9);
+        assert_relative_eq!(diff.max_concurrency.relative, 0.0);
+        assert_eq!(diff.verdict(0.05), Verdict::Improved);
+        assert_eq!(diff.verdict(0.2), Verdict::Unchanged);
+
+        let diff = baseline.compare(&baseline.scaled_kappa(4.0));
+        assert!(diff.max_concurrency.relative < 0.0);
+        assert_eq!(diff.verdict(0.05), Verdict::Regressed);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{Dataset, Measurement};
+
+/// A way of reducing a [Dataset] to fewer measurements, via [Dataset::downsample].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Downsample {
+    /// Keep the given number of measurements, evenly spaced through the dataset in its current
+    /// order.
+    ///
+    /// Each concurrency level keeps roughly the same share of the measurements it had, so the fit
+    /// is weighted towards the levels at which the system spent the most time, as it would be
+    /// for the full dataset. Sparse levels may be dropped entirely.
+    Uniform(usize),
+    /// Keep at most the given number of measurements from each concurrency level, evenly spaced
+    /// through each level's measurements in their current order.
+    ///
+    /// Every level is retained, and no level has more weight than any other. This corrects for
+    /// production telemetry being concentrated at typical loads, at the cost of over-weighting
+    /// rarely observed (and often noisier) levels relative to the full dataset.
+    Stratified(usize),
+    /// Keep the given number of measurements using the largest-triangle-three-buckets algorithm,
+    /// which selects the measurements that best preserve the shape of the throughput curve, in
+    /// order of concurrency.
+    ///
+    /// The measurements with the lowest and highest concurrency are always kept. The algorithm
+    /// favors extreme values, so it preserves peaks and retrograde regions well but biases the fit
+    /// towards outliers. Fewer than three measurements are selected as with
+    /// [Downsample::Uniform].
+    Lttb(usize),
+}
+
+impl Dataset {
+    /// Reduce the dataset to fewer measurements using the given strategy. The dataset is returned
+    /// unchanged if it's already small enough.
+    ///
+    /// ```
+    /// use usl::{Dataset, Downsample, Measurement};
+    ///
+    /// let dataset: Dataset = (1..=10_000)
+    ///     .map(|i| Measurement::concurrency_and_throughput(i % 32 + 1, 1000.0))
+    ///     .collect();
+    /// assert_eq!(dataset.downsample(Downsample::Uniform(100)).len(), 100);
+    /// assert_eq!(dataset.downsample(Downsample::Stratified(3)).len(), 96);
+    /// assert_eq!(dataset.downsample(Downsample::Lttb(100)).len(), 100);
+    /// ```
+    #[must_use]
+    pub fn downsample(&self, strategy: Downsample) -> Dataset {
+        let measurements = match strategy {
+            Downsample::Uniform(len) => evenly_spaced(self, len),
+            Downsample::Stratified(len) => {
+                self.by_concurrency().iter().flat_map(|group| evenly_spaced(group, len)).collect()
+            }
+            Downsample::Lttb(len) if len < 3 => evenly_spaced(self, len),
+            Downsample::Lttb(len) => {
+                let mut sorted = self.clone();
+                sorted.sort_by_concurrency();
+                lttb(&sorted, len)
+            }
+        };
+        Dataset::new(measurements).with_metadata(self.metadata().clone())
+    }
+}
+
+fn evenly_spaced(measurements: &[Measurement], len: usize) -> Vec<Measurement> {
+    if measurements.len() <= len {
+        return measurements.to_vec();
+    }
+    (0..len).map(|i| measurements[i * measurements.len() / len]).collect()
+}
+
+/// Select `len` of the given measurements, sorted by concurrency, via largest-triangle-three-
+/// buckets. See Steinarsson, "Downsampling Time Series for Visual Representation", 2013.
+fn lttb(measurements: &[Measurement], len: usize) -> Vec<Measurement> {
+    if measurements.len() <= len {
+        return measurements.to_vec();
+    }
+
+    // The first and last measurements are kept, and the rest are divided into len - 2 buckets,
+    // from each of which the measurement forming the largest triangle with the previously
+    // selected measurement and the average of the next bucket is kept.
+    let size = (measurements.len() - 2) as f64 / (len - 2) as f64;
+    let bucket = |i: usize| {
+        let start = (i as f64 * size) as usize + 1;
+        let end = (((i + 1) as f64 * size) as usize + 1).min(measurements.len() - 1);
+        &measurements[start..end]
+    };
+
+    let mut selected = Vec::with_capacity(len);
+    selected.push(measurements[0]);
+    let mut a = measurements[0];
+    for i in 0..len - 2 {
+        let next =
+            if i + 1 < len - 2 { bucket(i + 1) } else { &measurements[measurements.len() - 1..] };
+        let k = next.len() as f64;
+        let (cn, cx) =
+            (next.iter().map(|m| m.n).sum::<f64>() / k, next.iter().map(|m| m.x).sum::<f64>() / k);
+        let area = |b: &Measurement| ((a.n - cn) * (b.x - a.x) - (a.n - b.n) * (cx - a.x)).abs();
+        a = *bucket(i)
+            .iter()
+            .max_by(|p, q| area(p).total_cmp(&area(q)))
+            .expect("buckets are never empty");
+        selected.push(a);
+    }
+    selected.push(measurements[measurements.len() - 1]);
+    selected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn downsample() {
+        let dataset: Dataset = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        assert_eq!(dataset.downsample(Downsample::Uniform(64)).len(), 32);
+
+        let uniform = dataset.downsample(Downsample::Uniform(8));
+        assert_eq!(
+            uniform.iter().map(|m| m.n).collect::<Vec<f64>>(),
+            vec![1.0, 5.0, 9.0, 13.0, 17.0, 21.0, 25.0, 29.0]
+        );
+
+        let mut repeated: Dataset = dataset.iter().chain(dataset.iter()).copied().collect();
+        repeated.extend(dataset.iter().take(4).copied());
+        let stratified = repeated.downsample(Downsample::Stratified(1));
+        assert_eq!(stratified.len(), 32);
+        assert_eq!(stratified.statistics().levels, 32);
+
+        let lttb = dataset.downsample(Downsample::Lttb(6));
+        assert_eq!(lttb.len(), 6);
+        assert_eq!(lttb[0].n, 1.0);
+        assert_eq!(lttb[5].n, 32.0);
+        assert!(lttb.iter().zip(lttb.iter().skip(1)).all(|(a, b)| a.n < b.n));
+        assert_eq!(dataset.downsample(Downsample::Lttb(2)).len(), 2);
+    }
+}
@@ -0,0 +1,190 @@
+//! Detection of significant shifts in a system's scalability parameters over time.
+
+use std::time::Duration;
+
+use crate::{
+    normal_quantile, Change, Measurement, Model, ModelFitter, StandardErrors, TimedMeasurement,
+    Window, MIN_MEASUREMENTS,
+};
+
+/// A model fitted to the measurements in one window of time, via [detect].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment {
+    /// The time of the earliest measurement in the window.
+    pub start: Duration,
+    /// The time of the latest measurement in the window.
+    pub end: Duration,
+    /// The number of measurements in the window.
+    pub len: usize,
+    /// The model fitted to the measurements in the window.
+    pub model: Model,
+    /// The standard errors of the model's parameters.
+    pub errors: StandardErrors,
+}
+
+/// One of a model's parameters.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Parameter {
+    /// The coefficient of contention, σ.
+    Sigma,
+    /// The coefficient of crosstalk, κ.
+    Kappa,
+    /// The throughput of the system given a single user, λ.
+    Lambda,
+}
+
+/// A statistically significant shift in one of a model's parameters between two consecutive
+/// segments, via [detect].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Shift {
+    /// The parameter which shifted.
+    pub parameter: Parameter,
+    /// The index of the segment after the shift.
+    pub segment: usize,
+    /// The time of the earliest measurement after the shift.
+    pub at: Duration,
+    /// The change in the parameter's value.
+    pub change: Change,
+    /// The size of the change in units of its standard error.
+    pub z: f64,
+}
+
+/// The result of detecting parameter drift, via [detect].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Drift {
+    /// The models fitted to each window, in order of time.
+    pub segments: Vec<Segment>,
+    /// The significant shifts between consecutive segments, in order of time.
+    pub shifts: Vec<Shift>,
+}
+
+/// Split a stream of measurements into consecutive, non-overlapping windows, fit a model to each,
+/// and flag the parameters which shift significantly between consecutive windows.
+///
+/// A shift is significant if the difference between the two fitted values is larger than would be
+/// expected, at the given confidence level (e.g. `0.95`), from the standard errors of the two
+/// fits. Windows containing too few measurements to estimate standard errors, or which can't be
+/// fitted, are skipped.
+///
+/// ```
+/// use std::time::Duration;
+/// use usl::Window;
+/// use usl::drift::{detect, Parameter};
+/// # let measurements = vec![];
+///
+/// let drift = detect(&measurements, Window::Duration(Duration::from_secs(3600)), 0.99);
+/// for shift in drift.shifts.iter().filter(|s| s.parameter == Parameter::Kappa) {
+///     println!("κ changed by {:.0}% at {:?}", shift.change.relative * 100.0, shift.at);
+/// }
+/// ```
+#[must_use]
+pub fn detect(measurements: &[TimedMeasurement], window: Window, confidence: f64) -> Drift {
+    assert!((0.0..1.0).contains(&confidence), "confidence must be between 0 and 1");
+    let mut sorted = measurements.to_vec();
+    sorted.sort_by_key(|m| m.time);
+
+    let mut segments = Vec::new();
+    let mut start = 0;
+    while start < sorted.len() {
+        let end = match window {
+            Window::Count(count) => {
+                assert!(count > 0, "window must contain measurements");
+                (start + count).min(sorted.len())
+            }
+            Window::Duration(duration) => {
+                start
+                    + sorted[start..]
+                        .iter()
+                        .take_while(|m| m.time - sorted[start].time <= duration)
+                        .count()
+            }
+        };
+        segments.extend(segment(&sorted[start..end]));
+        start = end;
+    }
+
+    let critical = normal_quantile(1.0 - (1.0 - confidence) / 2.0);
+    let mut shifts = Vec::new();
+    for (i, pair) in segments.windows(2).enumerate() {
+        let (a, b) = (&pair[0], &pair[1]);
+        for (parameter, before, after, se_before, se_after) in [
+            (Parameter::Sigma, a.model.sigma, b.model.sigma, a.errors.sigma, b.errors.sigma),
+            (Parameter::Kappa, a.model.kappa, b.model.kappa, a.errors.kappa, b.errors.kappa),
+            (Parameter::Lambda, a.model.lambda, b.model.lambda, a.errors.lambda, b.errors.lambda),
+        ] {
+            let z = (after - before) / se_before.hypot(se_after);
+            if z.abs() > critical {
+                shifts.push(Shift {
+                    parameter,
+                    segment: i + 1,
+                    at: b.start,
+                    change: Change::new(before, after),
+                    z,
+                });
+            }
+        }
+    }
+    Drift { segments, shifts }
+}
+
+fn segment(window: &[TimedMeasurement]) -> Option<Segment> {
+    // At least one degree of freedom is needed to estimate the standard errors.
+    if window.len() <= MIN_MEASUREMENTS {
+        return None;
+    }
+    let measurements: Vec<Measurement> = window.iter().map(|m| m.measurement).collect();
+    let (model, report) =
+        Model::try_fit_with_report(&ModelFitter::new(&measurements), None).ok()?;
+    Some(Segment {
+        start: window[0].time,
+        end: window[window.len() - 1].time,
+        len: window.len(),
+        model,
+        errors: StandardErrors::from_report(&report),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+    use crate::NoiseSpec;
+
+    #[test]
+    fn detect_kappa_shift() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        let regressed = model.scaled_kappa(2.0);
+        let concurrency: Vec<u32> = (1..=64).collect();
+        let noise = NoiseSpec::Proportional { cv: 0.01 };
+        let mut rng = StdRng::seed_from_u64(0xDEADBEEF);
+
+        let mut measurements = Vec::new();
+        for (hour, model) in [model, model, regressed, regressed].iter().enumerate() {
+            measurements.extend(model.generate(&concurrency, noise, &mut rng).into_iter().map(
+                |m| TimedMeasurement::new(Duration::from_secs(hour as u64 * 3600 + m.n as u64), m),
+            ));
+        }
+
+        let drift = detect(&measurements, Window::Duration(Duration::from_secs(1800)), 0.999);
+        assert_eq!(drift.segments.len(), 4);
+        assert!(drift.segments.iter().all(|s| s.len == 64));
+
+        let kappa: Vec<&Shift> =
+            drift.shifts.iter().filter(|s| s.parameter == Parameter::Kappa).collect();
+        assert_eq!(kappa.len(), 1);
+        assert_eq!(kappa[0].segment, 2);
+        assert_eq!(kappa[0].at, Duration::from_secs(2 * 3600 + 1));
+        assert!(kappa[0].change.relative > 0.5 && kappa[0].change.relative < 1.5);
+        assert!(kappa[0].z > 0.0);
+
+        let drift = detect(&measurements, Window::Count(64), 0.999);
+        assert_eq!(drift.segments.len(), 4);
+    }
+}
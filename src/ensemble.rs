@@ -0,0 +1,124 @@
+use crate::{Bootstrap, Model};
+
+/// The spread of a quantity, e.g. as predicted by each of the models in an [Ensemble] or as
+/// observed in each run of a [simulation](crate::sim::simulate).
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Spread {
+    /// The mean value.
+    pub mean: f64,
+    /// The sample standard deviation of the values. Zero if there is only one value.
+    pub std_dev: f64,
+    /// The lowest value.
+    pub min: f64,
+    /// The highest value.
+    pub max: f64,
+}
+
+impl Spread {
+    /// Calculate the spread of the given values, which must not be empty.
+    pub(crate) fn of(values: &[f64]) -> Spread {
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let std_dev = if values.len() > 1 {
+            (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+        Spread {
+            mean,
+            std_dev,
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+
+    /// The coefficient of variation of the values, i.e. the standard deviation relative to the
+    /// mean.
+    #[must_use]
+    pub fn coefficient_of_variation(&self) -> f64 {
+        self.std_dev / self.mean
+    }
+}
+
+/// A set of models of the same system, e.g. from bootstrap resamples or from benchmarks run on
+/// different days, whose predictions are averaged.
+///
+/// Single fits of noisy data can vary considerably from run to run; averaging the predictions of
+/// several fits gives more stable results, and their spread indicates how much to trust them.
+///
+/// ```
+/// use usl::{Ensemble, Model};
+///
+/// let ensemble = Ensemble::new(vec![
+///     Model { sigma: 0.02, kappa: 0.0001, lambda: 1000.0 },
+///     Model { sigma: 0.03, kappa: 0.0002, lambda: 950.0 },
+/// ]);
+/// let x = ensemble.throughput_at_concurrency(32);
+/// println!("X(32) = {:.0} ± {:.0}", x.mean, x.std_dev);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ensemble {
+    /// The models in the ensemble.
+    pub models: Vec<Model>,
+}
+
+impl Ensemble {
+    /// Create an ensemble of the given models.
+    ///
+    /// Panics if there are no models.
+    #[must_use]
+    pub fn new(models: Vec<Model>) -> Ensemble {
+        assert!(!models.is_empty(), "no models");
+        Ensemble { models }
+    }
+
+    /// Calculate the spread of an arbitrary function of the models, e.g. [Model::max_throughput].
+    #[must_use]
+    pub fn predict(&self, f: impl Fn(&Model) -> f64) -> Spread {
+        Spread::of(&self.models.iter().map(f).collect::<Vec<f64>>())
+    }
+
+    /// Calculate the spread of the expected throughput given a number of concurrent events.
+    #[must_use]
+    pub fn throughput_at_concurrency(&self, n: u32) -> Spread {
+        self.predict(|m| m.throughput_at_concurrency(n))
+    }
+
+    /// Calculate the spread of the expected mean latency given a number of concurrent events.
+    #[must_use]
+    pub fn latency_at_concurrency(&self, n: u32) -> Spread {
+        self.predict(|m| m.latency_at_concurrency(n))
+    }
+}
+
+impl From<Bootstrap> for Ensemble {
+    fn from(bootstrap: Bootstrap) -> Self {
+        Ensemble::new(bootstrap.models)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn predict() {
+        let a = Model { sigma: 0.02, kappa: 0.0001, lambda: 1000.0 };
+        let b = Model { sigma: 0.03, kappa: 0.0002, lambda: 950.0 };
+        let ensemble = Ensemble::new(vec![a, b]);
+
+        let x = ensemble.throughput_at_concurrency(32);
+        let (xa, xb) = (a.throughput_at_concurrency(32), b.throughput_at_concurrency(32));
+        assert_relative_eq!(x.mean, (xa + xb) / 2.0);
+        assert_relative_eq!(x.std_dev, (xa - xb).abs() / 2f64.sqrt());
+        assert_relative_eq!(x.min, xa.min(xb));
+        assert_relative_eq!(x.max, xa.max(xb));
+
+        let r = Ensemble::new(vec![a]).latency_at_concurrency(32);
+        assert_relative_eq!(r.mean, a.latency_at_concurrency(32));
+        assert_relative_eq!(r.std_dev, 0.0);
+    }
+}
@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::{FitError, Measurement, MIN_MEASUREMENTS};
+
+/// A problem with an individual measurement which prevents it from being used to build a model.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// The measurement has a NaN or infinite value, e.g. from a malformed row of a CSV file.
+    NonFinite,
+    /// The measurement's concurrency is zero or negative.
+    NonPositiveConcurrency,
+    /// The measurement's throughput is zero or negative.
+    NonPositiveThroughput,
+    /// The measurement's latency is zero or negative.
+    NonPositiveLatency,
+    /// The measurement is identical to an earlier measurement, at the given index, and too few of
+    /// the measurements are distinct to build a model.
+    Duplicate(usize),
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::NonFinite => f.write_str("non-finite value"),
+            Problem::NonPositiveConcurrency => f.write_str("non-positive concurrency"),
+            Problem::NonPositiveThroughput => f.write_str("non-positive throughput"),
+            Problem::NonPositiveLatency => f.write_str("non-positive latency"),
+            Problem::Duplicate(i) => write!(f, "duplicate of measurement {}", i),
+        }
+    }
+}
+
+impl Error for Problem {}
+
+impl Problem {
+    /// Check a single measurement, returning the first problem found with its values.
+    ///
+    /// Non-positive values are reported before non-finite ones, so zero throughput is reported as
+    /// such rather than as the infinite latency derived from it.
+    pub(crate) fn of(m: &Measurement) -> Option<Problem> {
+        if m.n <= 0.0 {
+            Some(Problem::NonPositiveConcurrency)
+        } else if m.x <= 0.0 {
+            Some(Problem::NonPositiveThroughput)
+        } else if m.r <= 0.0 {
+            Some(Problem::NonPositiveLatency)
+        } else if !(m.n.is_finite() && m.x.is_finite() && m.r.is_finite()) {
+            Some(Problem::NonFinite)
+        } else {
+            None
+        }
+    }
+}
+
+/// An error returned when a set of measurements cannot be used to build a model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// There were fewer than [MIN_MEASUREMENTS] measurements.
+    TooFewMeasurements(usize),
+    /// Some measurements were invalid. Each is given by its index and the first problem found with
+    /// it, in order of index.
+    InvalidMeasurements(Vec<(usize, Problem)>),
+}
+
+impl ValidationError {
+    /// Check the given measurements, returning an error if any of them would poison a fit.
+    ///
+    /// Repeated measurements are legitimate replicates, and are only reported as duplicates if
+    /// there are fewer than [MIN_MEASUREMENTS] distinct measurements, in which case the repeats
+    /// merely pad out an underdetermined fit.
+    pub fn check(measurements: &[Measurement]) -> Result<(), ValidationError> {
+        let mut seen = HashMap::with_capacity(measurements.len());
+        let mut invalid = Vec::new();
+        let mut duplicates = Vec::new();
+        for (i, m) in measurements.iter().enumerate() {
+            if let Some(problem) = Problem::of(m) {
+                invalid.push((i, problem));
+            } else {
+                let key = [m.n.to_bits(), m.x.to_bits(), m.r.to_bits()];
+                if let Some(&first) = seen.get(&key) {
+                    duplicates.push((i, Problem::Duplicate(first)));
+                } else {
+                    seen.insert(key, i);
+                }
+            }
+        }
+
+        if seen.len() < MIN_MEASUREMENTS && measurements.len() >= MIN_MEASUREMENTS {
+            invalid.extend(duplicates);
+            invalid.sort_by_key(|&(i, _)| i);
+        }
+
+        if !invalid.is_empty() {
+            Err(ValidationError::InvalidMeasurements(invalid))
+        } else if measurements.len() < MIN_MEASUREMENTS {
+            Err(ValidationError::TooFewMeasurements(measurements.len()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::TooFewMeasurements(n) => {
+                write!(f, "must have at least {} measurements, but have {}", MIN_MEASUREMENTS, n)
+            }
+            ValidationError::InvalidMeasurements(invalid) => {
+                f.write_str("invalid measurements: ")?;
+                for (j, (i, problem)) in invalid.iter().enumerate() {
+                    if j > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{} ({})", i, problem)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+/// An error returned by [Model::try_build](crate::Model::try_build).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// The measurements were invalid.
+    Invalid(ValidationError),
+    /// The measurements were valid, but the model could not be fitted to them.
+    Fit(FitError),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::Invalid(err) => err.fmt(f),
+            BuildError::Fit(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for BuildError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BuildError::Invalid(err) => Some(err),
+            BuildError::Fit(err) => Some(err),
+        }
+    }
+}
+
+impl From<ValidationError> for BuildError {
+    fn from(err: ValidationError) -> Self {
+        BuildError::Invalid(err)
+    }
+}
+
+impl From<FitError> for BuildError {
+    fn from(err: FitError) -> Self {
+        BuildError::Fit(err)
+    }
+}
+
+/// An error returned when a model is asked to achieve a throughput higher than its peak.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Infeasible {
+    /// The requested throughput.
+    pub throughput: f64,
+    /// The model's maximum throughput.
+    pub max_throughput: f64,
+}
+
+impl fmt::Display for Infeasible {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "throughput of {} exceeds maximum throughput of {}",
+            self.throughput, self.max_throughput
+        )
+    }
+}
+
+impl Error for Infeasible {}
+
+/// An error returned when a model's inverse functions are asked about a throughput at which they
+/// have no meaningful value, via e.g.
+/// [Model::checked_latency_at_throughput](crate::Model::checked_latency_at_throughput).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum InverseError {
+    /// The throughput is negative or not finite.
+    InvalidThroughput(f64),
+    /// The throughput is higher than the peak of the throughput curve, so the system can never
+    /// achieve it.
+    AbovePeak {
+        /// The requested throughput.
+        throughput: f64,
+        /// The model's maximum throughput.
+        max_throughput: f64,
+    },
+    /// The throughput is at or beyond the asymptotic limit of the model's contention, `λ/σ`, where
+    /// the inverse functions are singular.
+    Singularity {
+        /// The requested throughput.
+        throughput: f64,
+        /// The asymptotic limit, `λ/σ`.
+        asymptote: f64,
+    },
+}
+
+impl fmt::Display for InverseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InverseError::InvalidThroughput(x) => write!(f, "invalid throughput of {}", x),
+            InverseError::AbovePeak { throughput, max_throughput } => write!(
+                f,
+                "throughput of {} exceeds maximum throughput of {}",
+                throughput, max_throughput
+            ),
+            InverseError::Singularity { throughput, asymptote } => write!(
+                f,
+                "throughput of {} is at or beyond the asymptotic limit of {}",
+                throughput, asymptote
+            ),
+        }
+    }
+}
+
+impl Error for InverseError {}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn check() {
+        let mut measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        assert_eq!(ValidationError::check(&measurements), Ok(()));
+        assert_eq!(
+            ValidationError::check(&measurements[..3]),
+            Err(ValidationError::TooFewMeasurements(3))
+        );
+
+        measurements[2].x = f64::NAN;
+        measurements[5] = Measurement::concurrency_and_throughput(6, 0.0);
+        measurements[7].n = 0.0;
+        measurements[9] = Measurement::concurrency_and_latency(10, Duration::ZERO);
+        measurements[12] = measurements[11];
+        measurements[15].r = 0.0;
+        measurements[20] = measurements[11];
+        assert_eq!(
+            ValidationError::check(&measurements),
+            Err(ValidationError::InvalidMeasurements(vec![
+                (2, Problem::NonFinite),
+                (5, Problem::NonPositiveThroughput),
+                (7, Problem::NonPositiveConcurrency),
+                (9, Problem::NonPositiveLatency),
+                (15, Problem::NonPositiveLatency),
+            ]))
+        );
+
+        // Replicates are fine, unless they're all that makes up the minimum number of measurements.
+        let replicated: Vec<Measurement> =
+            MEASUREMENTS.iter().chain(MEASUREMENTS.iter()).map(|&m| m.into()).collect();
+        assert_eq!(ValidationError::check(&replicated), Ok(()));
+        let padded: Vec<Measurement> =
+            [(1, 65.0), (2, 120.0), (1, 65.0), (3, 170.0), (1, 65.0), (2, 120.0)]
+                .iter()
+                .map(|&m| m.into())
+                .collect();
+        assert_eq!(
+            ValidationError::check(&padded),
+            Err(ValidationError::InvalidMeasurements(vec![
+                (2, Problem::Duplicate(0)),
+                (4, Problem::Duplicate(0)),
+                (5, Problem::Duplicate(1)),
+            ]))
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{Measurement, Model};
+
+/// A model's error in predicting the throughput of a single held-out measurement.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PointError {
+    /// The held-out measurement.
+    pub measurement: Measurement,
+    /// The model's predicted throughput at the measurement's concurrency.
+    pub predicted: f64,
+    /// The observed throughput less the predicted throughput.
+    pub error: f64,
+    /// The error as a fraction of the observed throughput.
+    pub relative_error: f64,
+}
+
+/// An evaluation of a model against measurements it was not fitted to, via [Model::evaluate].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EvalReport {
+    /// The model's error at each held-out measurement, in the order given.
+    pub points: Vec<PointError>,
+    /// The mean error. A positive bias means the model underestimates throughput.
+    pub bias: f64,
+    /// The root mean squared error.
+    pub rmse: f64,
+    /// The mean absolute error.
+    pub mae: f64,
+    /// The mean absolute relative error.
+    pub mape: f64,
+    /// The largest absolute relative error.
+    pub max_relative_error: f64,
+    /// The out-of-sample coefficient of determination, R², which is negative if the model predicts
+    /// the held-out measurements worse than their mean does.
+    pub r_squared: f64,
+}
+
+impl Model {
+    /// Evaluate the model's predicted throughput against a separate set of held-out measurements,
+    /// e.g. from a different cluster than the one the model was fitted to.
+    ///
+    /// Unlike [Model::fit_quality], which describes how well a model fits its own training data,
+    /// this describes how well it generalizes.
+    ///
+    /// Panics if there are no held-out measurements.
+    #[must_use]
+    pub fn evaluate(&self, holdout: &[Measurement]) -> EvalReport {
+        assert!(!holdout.is_empty(), "no held-out measurements");
+        let points: Vec<PointError> = holdout
+            .iter()
+            .map(|&m| {
+                let predicted = self.throughput_at_concurrency_f64(m.n);
+                let error = m.x - predicted;
+                PointError { measurement: m, predicted, error, relative_error: error / m.x }
+            })
+            .collect();
+
+        let n = points.len() as f64;
+        let mean = holdout.iter().map(|m| m.x).sum::<f64>() / n;
+        let ss_res: f64 = points.iter().map(|p| p.error * p.error).sum();
+        let ss_tot: f64 = holdout.iter().map(|m| (m.x - mean).powi(2)).sum();
+        EvalReport {
+            bias: points.iter().map(|p| p.error).sum::<f64>() / n,
+            rmse: (ss_res / n).sqrt(),
+            mae: points.iter().map(|p| p.error.abs()).sum::<f64>() / n,
+            mape: points.iter().map(|p| p.relative_error.abs()).sum::<f64>() / n,
+            max_relative_error: points.iter().map(|p| p.relative_error.abs()).fold(0.0, f64::max),
+            r_squared: 1.0 - ss_res / ss_tot,
+            points,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn evaluate() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let (train, holdout): (Vec<_>, Vec<_>) =
+            measurements.iter().enumerate().partition(|(i, _)| i % 3 != 0);
+        let train: Vec<Measurement> = train.into_iter().map(|(_, &m)| m).collect();
+        let holdout: Vec<Measurement> = holdout.into_iter().map(|(_, &m)| m).collect();
+
+        let model = Model::build(&train);
+        let report = model.evaluate(&holdout);
+        assert_eq!(report.points.len(), holdout.len());
+        assert!(report.r_squared > 0.9 && report.r_squared < 1.0);
+        assert!(report.mae > 0.0 && report.mae <= report.rmse);
+        assert!(report.mape > 0.0 && report.mape <= report.max_relative_error);
+        assert!(report.bias.abs() <= report.mae);
+
+        let perfect: Vec<Measurement> = (1..=10)
+            .map(|n| Measurement::concurrency_and_throughput(n, model.throughput_at_concurrency(n)))
+            .collect();
+        let report = model.evaluate(&perfect);
+        assert_relative_eq!(report.rmse, 0.0);
+        assert_relative_eq!(report.max_relative_error, 0.0);
+    }
+}
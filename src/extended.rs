@@ -0,0 +1,143 @@
+use crate::fitter::minimize_with_fallback;
+use crate::{Measurement, Model, Objective, ScalabilityModel, MIN_MEASUREMENTS};
+
+/// An extended Universal Scalability Law model with a fourth parameter, γ, which stretches the
+/// coherency term: `X(N) = λN/(1 + σ(N-1) + κ(N(N-1))^γ)`.
+///
+/// When γ is 1, this is the same as [Model]. Systems whose coherency costs grow more or less
+/// steeply than quadratically are better described with γ above or below 1, respectively.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExtendedModel {
+    /// The model's coefficient of contention, σ.
+    pub sigma: f64,
+    /// The model's coefficient of crosstalk/coherency, κ.
+    pub kappa: f64,
+    /// The model's coefficient of performance, λ.
+    pub lambda: f64,
+    /// The exponent of the model's coherency term, γ.
+    pub gamma: f64,
+}
+
+impl ExtendedModel {
+    /// Build a model whose parameters are generated from the given measurements.
+    ///
+    /// The fit starts from the parameters of a [Model] fitted to the same measurements, with γ at
+    /// 1.
+    #[must_use]
+    pub fn build(measurements: &[Measurement]) -> ExtendedModel {
+        assert!(
+            measurements.len() > MIN_MEASUREMENTS,
+            "must have more than {} measurements",
+            MIN_MEASUREMENTS
+        );
+        let start = Model::build(measurements);
+        let mut params = [start.sigma, start.kappa, start.lambda, 1.0];
+        if let Err(err) = minimize_with_fallback(&ExtendedFitter(measurements), &mut params) {
+            panic!("{}", err)
+        }
+        ExtendedModel { sigma: params[0], kappa: params[1], lambda: params[2], gamma: params[3] }
+    }
+
+    /// Calculate the expected throughput given a number of concurrent events, `X(N)`.
+    #[must_use]
+    pub fn throughput_at_concurrency(&self, n: u32) -> f64 {
+        self.throughput_at_concurrency_f64(n.into())
+    }
+
+    /// Calculate the expected throughput given a possibly fractional average number of concurrent
+    /// events, `X(N)`.
+    #[must_use]
+    pub fn throughput_at_concurrency_f64(&self, n: f64) -> f64 {
+        self.lambda * n
+            / (1.0 + self.sigma * (n - 1.0) + self.kappa * (n * (n - 1.0)).powf(self.gamma))
+    }
+
+    /// Calculate the expected mean latency given a number of concurrent events, `R(N)`.
+    #[must_use]
+    pub fn latency_at_concurrency(&self, n: u32) -> f64 {
+        f64::from(n) / self.throughput_at_concurrency(n)
+    }
+}
+
+impl From<Model> for ExtendedModel {
+    fn from(model: Model) -> Self {
+        ExtendedModel { sigma: model.sigma, kappa: model.kappa, lambda: model.lambda, gamma: 1.0 }
+    }
+}
+
+impl ScalabilityModel for ExtendedModel {
+    fn throughput_at_concurrency(&self, n: u32) -> f64 {
+        ExtendedModel::throughput_at_concurrency(self, n)
+    }
+
+    fn throughput_at_concurrency_f64(&self, n: f64) -> f64 {
+        ExtendedModel::throughput_at_concurrency_f64(self, n)
+    }
+
+    fn parameters(&self) -> usize {
+        4
+    }
+}
+
+struct ExtendedFitter<'a>(&'a [Measurement]);
+
+impl Objective for ExtendedFitter<'_> {
+    fn number_of_points(&self) -> usize {
+        self.0.len()
+    }
+
+    fn residuals(&self, params: &[f64], residuals: &mut [f64]) {
+        let model = ExtendedModel {
+            sigma: params[0],
+            kappa: params[1],
+            lambda: params[2],
+            gamma: params[3],
+        };
+        for (r, m) in residuals.iter_mut().zip(self.0) {
+            *r = m.x - model.throughput_at_concurrency_f64(m.n);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::compare::information_criteria;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn build() {
+        let expected = ExtendedModel { sigma: 0.02, kappa: 0.0002, lambda: 1000.0, gamma: 1.3 };
+        let measurements: Vec<Measurement> = (1..=40)
+            .map(|n| {
+                Measurement::concurrency_and_throughput(n, expected.throughput_at_concurrency(n))
+            })
+            .collect();
+
+        let model = ExtendedModel::build(&measurements);
+        assert_relative_eq!(model.sigma, expected.sigma, max_relative = 0.001);
+        assert_relative_eq!(model.kappa, expected.kappa, max_relative = 0.001);
+        assert_relative_eq!(model.lambda, expected.lambda, max_relative = 0.001);
+        assert_relative_eq!(model.gamma, expected.gamma, max_relative = 0.001);
+
+        let usl = Model::build(&measurements);
+        let scores = information_criteria(&[&usl, &model], &measurements);
+        assert!(scores[1].rss < scores[0].rss);
+    }
+
+    #[test]
+    fn reduces_to_usl() {
+        let usl: Model = MEASUREMENTS.iter().collect();
+        let extended = ExtendedModel::from(usl);
+        for n in [1, 10, 35] {
+            assert_relative_eq!(
+                extended.throughput_at_concurrency(n),
+                usl.throughput_at_concurrency(n)
+            );
+            assert_relative_eq!(extended.latency_at_concurrency(n), usl.latency_at_concurrency(n));
+        }
+    }
+}
@@ -0,0 +1,364 @@
+use std::error::Error;
+use std::fmt;
+
+use rmpfit::{MPError, MPFitter, MPResult, MPStatus, MPSuccess};
+
+use crate::Model;
+
+/// A nonlinear least-squares problem: a function from a vector of parameters to a vector of
+/// residuals, one per data point.
+pub trait Objective {
+    /// The number of data points, and therefore residuals.
+    fn number_of_points(&self) -> usize;
+
+    /// Calculate the residuals of each data point given the parameters.
+    fn residuals(&self, params: &[f64], residuals: &mut [f64]);
+}
+
+/// A nonlinear regression backend which can fit a [Model](crate::Model) to measurements.
+///
+/// The default backend is [Lma], which uses the Levenberg-Marquardt implementation in `rmpfit`.
+/// Other optimizers can be used via [Model::build_with_fitter](crate::Model::build_with_fitter).
+pub trait Fitter {
+    /// Refine the given parameters, in place, to minimize the sum of the objective's squared
+    /// residuals.
+    fn minimize(&self, objective: &dyn Objective, params: &mut [f64]) -> Result<(), FitError>;
+}
+
+/// An error returned by a [Fitter] which was unable to minimize an [Objective].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FitError {
+    reason: String,
+}
+
+impl FitError {
+    /// Create a new error with the given reason.
+    pub fn new(reason: impl Into<String>) -> FitError {
+        FitError { reason: reason.into() }
+    }
+}
+
+impl fmt::Display for FitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.reason)
+    }
+}
+
+impl Error for FitError {}
+
+impl From<MPError> for FitError {
+    fn from(err: MPError) -> Self {
+        FitError::new(format!("lma error: {}", err))
+    }
+}
+
+/// Diagnostics describing how a model was fitted.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FitReport {
+    /// The parameters the fit started from.
+    pub initial: Model,
+    /// The reason the optimizer stopped.
+    pub termination: Termination,
+    /// The number of iterations used.
+    pub iterations: usize,
+    /// The number of evaluations of the objective function.
+    pub evaluations: usize,
+    /// The sum of squared residuals for the initial parameters.
+    pub initial_chi_square: f64,
+    /// The sum of squared residuals for the fitted parameters.
+    pub chi_square: f64,
+    /// The number of parameters which were free to vary.
+    pub free_parameters: usize,
+    /// The number of free parameters which ended the fit pegged at one of their bounds.
+    pub pegged_parameters: usize,
+    /// The number of residuals the fit minimized.
+    pub points: usize,
+    /// The covariance matrix of σ, κ, and λ, in that order, assuming unit variance residuals. All
+    /// values are NaN if the optimizer did not compute a covariance matrix.
+    pub covariance: [[f64; 3]; 3],
+}
+
+/// The standard errors of a model's parameters.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StandardErrors {
+    /// The standard error of σ.
+    pub sigma: f64,
+    /// The standard error of κ.
+    pub kappa: f64,
+    /// The standard error of λ.
+    pub lambda: f64,
+    /// The covariance matrix of σ, κ, and λ, in that order.
+    pub covariance: [[f64; 3]; 3],
+}
+
+impl StandardErrors {
+    /// Calculate the standard errors of a fit's parameters.
+    ///
+    /// The covariance matrix is scaled by the reduced chi-square of the fit, since the true
+    /// variance of the measurements is unknown.
+    #[must_use]
+    pub fn from_report(report: &FitReport) -> StandardErrors {
+        let dof = report.points.saturating_sub(report.free_parameters) as f64;
+        let scale = report.chi_square / dof;
+        let mut covariance = report.covariance;
+        for row in covariance.iter_mut() {
+            for v in row.iter_mut() {
+                *v *= scale;
+            }
+        }
+        StandardErrors {
+            sigma: covariance[0][0].sqrt(),
+            kappa: covariance[1][1].sqrt(),
+            lambda: covariance[2][2].sqrt(),
+            covariance,
+        }
+    }
+}
+
+/// The reason an optimizer stopped fitting a model.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Termination {
+    /// The relative reduction in chi-square fell below the convergence criterion.
+    ChiSquare,
+    /// The relative change in the parameters fell below the convergence criterion.
+    Parameters,
+    /// Both chi-square and the parameters converged.
+    Both,
+    /// The residuals became orthogonal to the Jacobian's columns.
+    Orthogonality,
+    /// The maximum number of iterations was reached without converging.
+    MaxIterations,
+    /// No further reduction in chi-square is possible at the requested tolerance.
+    ChiSquareTolerance,
+    /// No further improvement in the parameters is possible at the requested tolerance.
+    ParameterTolerance,
+    /// No further improvement in orthogonality is possible at the requested tolerance.
+    OrthogonalityTolerance,
+    /// The Levenberg-Marquardt fit failed and the model was fitted with [NelderMead] instead, which
+    /// converged. If it instead reached its maximum number of iterations, the termination is
+    /// [Termination::MaxIterations].
+    NelderMead,
+}
+
+impl Termination {
+    /// Whether or not the optimizer stopped because it met a convergence criterion.
+    #[must_use]
+    pub const fn converged(&self) -> bool {
+        matches!(
+            self,
+            Termination::ChiSquare
+                | Termination::Parameters
+                | Termination::Both
+                | Termination::Orthogonality
+                | Termination::NelderMead
+        )
+    }
+}
+
+impl FitReport {
+    pub(crate) fn from_status(initial: Model, status: &MPStatus) -> FitReport {
+        FitReport {
+            initial,
+            termination: match status.success {
+                MPSuccess::Chi => Termination::ChiSquare,
+                MPSuccess::Par => Termination::Parameters,
+                MPSuccess::Both => Termination::Both,
+                MPSuccess::Dir => Termination::Orthogonality,
+                MPSuccess::MaxIter | MPSuccess::NotDone => Termination::MaxIterations,
+                MPSuccess::Ftol => Termination::ChiSquareTolerance,
+                MPSuccess::Xtol => Termination::ParameterTolerance,
+                MPSuccess::Gtol => Termination::OrthogonalityTolerance,
+            },
+            iterations: status.n_iter,
+            evaluations: status.n_fev,
+            initial_chi_square: status.orig_norm,
+            chi_square: status.best_norm,
+            free_parameters: status.n_free,
+            pegged_parameters: status.n_pegged,
+            points: status.n_func,
+            covariance: [
+                [status.covar[0], status.covar[1], status.covar[2]],
+                [status.covar[3], status.covar[4], status.covar[5]],
+                [status.covar[6], status.covar[7], status.covar[8]],
+            ],
+        }
+    }
+}
+
+/// A [Fitter] which uses the Levenberg-Marquardt algorithm as implemented by `rmpfit`.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Lma;
+
+impl Fitter for Lma {
+    fn minimize(&self, objective: &dyn Objective, params: &mut [f64]) -> Result<(), FitError> {
+        LmaObjective(objective).mpfit(params, None, &Default::default())?;
+        Ok(())
+    }
+}
+
+/// Minimize the objective with [Lma], falling back to [NelderMead] if that fails.
+pub(crate) fn minimize_with_fallback(
+    objective: &dyn Objective,
+    params: &mut [f64],
+) -> Result<(), FitError> {
+    let initial = params.to_vec();
+    Lma.minimize(objective, params).or_else(|err| {
+        params.copy_from_slice(&initial);
+        NelderMead::default().minimize(objective, params).map_err(|_| err)
+    })
+}
+
+pub(crate) struct LmaObjective<'a>(pub(crate) &'a dyn Objective);
+
+impl MPFitter for LmaObjective<'_> {
+    fn eval(&self, params: &[f64], deviates: &mut [f64]) -> MPResult<()> {
+        self.0.residuals(params, deviates);
+        Ok(())
+    }
+
+    fn number_of_points(&self) -> usize {
+        self.0.number_of_points()
+    }
+}
+
+/// A [Fitter] which uses the derivative-free Nelder-Mead simplex algorithm.
+///
+/// Slower and less precise than [Lma], but robust to objectives which produce non-finite values for
+/// some parameters. [Model::build](crate::Model::build) falls back to it if [Lma] fails.
+#[derive(Debug, Copy, Clone)]
+pub struct NelderMead {
+    /// The maximum number of iterations.
+    pub max_iter: usize,
+    /// The relative difference between the best and worst vertices' objective values at which the
+    /// simplex is considered to have converged.
+    pub ftol: f64,
+}
+
+impl Default for NelderMead {
+    fn default() -> Self {
+        NelderMead { max_iter: 10_000, ftol: 1e-12 }
+    }
+}
+
+impl NelderMead {
+    /// Minimize the objective, holding fixed any parameters for which `fixed` is `true` and
+    /// treating any parameters outside `bounds` as infinitely bad. Returns the number of iterations
+    /// and objective evaluations, and whether the simplex converged before the maximum number of
+    /// iterations.
+    pub(crate) fn minimize_constrained(
+        &self,
+        objective: &dyn Objective,
+        params: &mut [f64],
+        fixed: &[bool],
+        bounds: &[(f64, f64)],
+    ) -> Result<(usize, usize, bool), FitError> {
+        let mut residuals = vec![0.0; objective.number_of_points()];
+        let mut evaluations = 0;
+        let mut cost = |x: &[f64]| -> f64 {
+            evaluations += 1;
+            if x.iter().zip(bounds).any(|(v, &(lo, hi))| *v < lo || *v > hi) {
+                return f64::INFINITY;
+            }
+            objective.residuals(x, &mut residuals);
+            let sum: f64 = residuals.iter().map(|r| r * r).sum();
+            if sum.is_finite() {
+                sum
+            } else {
+                f64::INFINITY
+            }
+        };
+
+        // Build the initial simplex by perturbing each free parameter in turn. Fixed parameters
+        // have the same value in every vertex and so are never varied.
+        let mut simplex = vec![params.to_vec()];
+        for (i, _) in fixed.iter().enumerate().filter(|(_, &f)| !f) {
+            let mut v = params.to_vec();
+            v[i] = if v[i] == 0.0 { 0.00025 } else { v[i] * 1.05 };
+            simplex.push(v);
+        }
+        let mut costs: Vec<f64> = simplex.iter().map(|v| cost(v)).collect();
+
+        let mut iterations = 0;
+        let mut converged = false;
+        while iterations < self.max_iter {
+            iterations += 1;
+            if simplex.len() < 2 {
+                converged = true;
+                break;
+            }
+
+            let mut order: Vec<usize> = (0..simplex.len()).collect();
+            order.sort_by(|&a, &b| costs[a].total_cmp(&costs[b]));
+            simplex = order.iter().map(|&i| simplex[i].clone()).collect();
+            costs = order.iter().map(|&i| costs[i]).collect();
+
+            let (best, worst) = (costs[0], costs[costs.len() - 1]);
+            if (worst - best).abs() <= self.ftol * (best.abs() + f64::MIN_POSITIVE) {
+                converged = true;
+                break;
+            }
+
+            let n = simplex.len() - 1;
+            let centroid: Vec<f64> = (0..params.len())
+                .map(|j| simplex[..n].iter().map(|v| v[j]).sum::<f64>() / n as f64)
+                .collect();
+            let towards = |t: f64| -> Vec<f64> {
+                centroid.iter().zip(&simplex[n]).map(|(c, w)| c + t * (w - c)).collect()
+            };
+
+            let reflected = towards(-1.0);
+            let fr = cost(&reflected);
+            if fr < costs[0] {
+                let expanded = towards(-2.0);
+                let fe = cost(&expanded);
+                if fe < fr {
+                    simplex[n] = expanded;
+                    costs[n] = fe;
+                } else {
+                    simplex[n] = reflected;
+                    costs[n] = fr;
+                }
+            } else if fr < costs[n - 1] {
+                simplex[n] = reflected;
+                costs[n] = fr;
+            } else {
+                let contracted = if fr < costs[n] { towards(-0.5) } else { towards(0.5) };
+                let fc = cost(&contracted);
+                if fc < costs[n].min(fr) {
+                    simplex[n] = contracted;
+                    costs[n] = fc;
+                } else {
+                    // Shrink every vertex towards the best one.
+                    for i in 1..=n {
+                        let v: Vec<f64> = simplex[0]
+                            .iter()
+                            .zip(&simplex[i])
+                            .map(|(b, v)| b + 0.5 * (v - b))
+                            .collect();
+                        costs[i] = cost(&v);
+                        simplex[i] = v;
+                    }
+                }
+            }
+        }
+
+        let best = (0..simplex.len()).min_by(|&a, &b| costs[a].total_cmp(&costs[b])).unwrap();
+        if !costs[best].is_finite() {
+            return Err(FitError::new("nelder-mead error: no finite solution found"));
+        }
+        params.copy_from_slice(&simplex[best]);
+        Ok((iterations, evaluations, converged))
+    }
+}
+
+impl Fitter for NelderMead {
+    fn minimize(&self, objective: &dyn Objective, params: &mut [f64]) -> Result<(), FitError> {
+        let fixed = vec![false; params.len()];
+        let bounds = vec![(f64::NEG_INFINITY, f64::INFINITY); params.len()];
+        self.minimize_constrained(objective, params, &fixed, &bounds)?;
+        Ok(())
+    }
+}
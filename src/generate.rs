@@ -0,0 +1,169 @@
+use rand::Rng;
+
+use crate::{standard_normal, Dataset, Measurement, Model};
+
+/// The noise added to the throughput of synthetic measurements, via [Model::generate], or of
+/// existing measurements, via [Dataset::with_noise].
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NoiseSpec {
+    /// No noise; measurements lie exactly on the model's throughput curve.
+    #[default]
+    None,
+    /// Normally distributed noise with the given standard deviation, in events/sec.
+    Gaussian {
+        /// The standard deviation of the noise.
+        sd: f64,
+    },
+    /// Normally distributed noise with a standard deviation proportional to the throughput, e.g.
+    /// `0.05` for noise of 5%.
+    Proportional {
+        /// The coefficient of variation of the noise.
+        cv: f64,
+    },
+}
+
+impl Model {
+    /// Generate synthetic measurements of throughput at each of the given concurrency levels, with
+    /// the given noise, e.g. for testing how robustly models can be fitted.
+    ///
+    /// Large amounts of noise may produce measurements with negative throughput.
+    ///
+    /// ```
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    /// use usl::{Model, NoiseSpec};
+    ///
+    /// let model = Model { sigma: 0.02, kappa: 0.0001, lambda: 1000.0 };
+    /// let mut rng = StdRng::seed_from_u64(1);
+    /// let measurements = model.generate(&[1, 2, 4, 8, 16, 32, 64], NoiseSpec::Proportional { cv: 0.05 }, &mut rng);
+    /// let fitted = Model::build(&measurements);
+    /// ```
+    #[must_use]
+    pub fn generate(
+        &self,
+        concurrency: &[u32],
+        noise: NoiseSpec,
+        rng: &mut impl Rng,
+    ) -> Vec<Measurement> {
+        concurrency
+            .iter()
+            .map(|&n| {
+                let x = noise.apply(self.throughput_at_concurrency(n), rng);
+                Measurement::concurrency_and_throughput(n, x)
+            })
+            .collect()
+    }
+}
+
+impl Dataset {
+    /// Add the given noise to the throughput of each measurement, re-deriving its latency via
+    /// Little's Law, e.g. for testing how stable a model's parameters are under measurement error.
+    ///
+    /// Large amounts of noise may produce measurements with negative throughput.
+    ///
+    /// ```
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    /// use usl::{Dataset, Model, NoiseSpec};
+    ///
+    /// let dataset: Dataset = [
+    ///     (1, 65.0), (18, 996.0), (36, 1652.0), (72, 1853.0), (108, 1829.0), (144, 1775.0),
+    ///     (216, 1680.0),
+    /// ]
+    /// .iter()
+    /// .map(|&m| m.into())
+    /// .collect();
+    /// let mut rng = StdRng::seed_from_u64(1);
+    /// let peaks: Vec<u32> = (0..10)
+    ///     .map(|_| Model::build(&dataset.with_noise(NoiseSpec::Proportional { cv: 0.02 }, &mut rng)))
+    ///     .map(|model| model.max_concurrency())
+    ///     .collect();
+    /// println!("N_max: {:?}", peaks);
+    /// ```
+    #[must_use]
+    pub fn with_noise(&self, noise: NoiseSpec, rng: &mut impl Rng) -> Dataset {
+        self.iter()
+            .map(|m| {
+                let x = noise.apply(m.x, rng);
+                Measurement { n: m.n, x, r: m.n / x }
+            })
+            .collect::<Dataset>()
+            .with_metadata(self.metadata().clone())
+    }
+}
+
+impl NoiseSpec {
+    fn apply(self, x: f64, rng: &mut impl Rng) -> f64 {
+        match self {
+            NoiseSpec::None => x,
+            NoiseSpec::Gaussian { sd } => x + sd * standard_normal(rng),
+            NoiseSpec::Proportional { cv } => x * (1.0 + cv * standard_normal(rng)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn generate() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        let concurrency: Vec<u32> = (1..=32).collect();
+        let mut rng = StdRng::seed_from_u64(0xDEADBEEF);
+
+        let exact = model.generate(&concurrency, NoiseSpec::None, &mut rng);
+        for m in &exact {
+            assert_relative_eq!(m.x, model.throughput_at_concurrency_f64(m.n));
+        }
+
+        let noisy = model.generate(&concurrency, NoiseSpec::Proportional { cv: 0.01 }, &mut rng);
+        let errors: Vec<f64> =
+            noisy.iter().map(|m| m.x / model.throughput_at_concurrency_f64(m.n) - 1.0).collect();
+        let sd = (errors.iter().map(|e| e * e).sum::<f64>() / errors.len() as f64).sqrt();
+        assert!(sd > 0.005 && sd < 0.02, "{}", sd);
+
+        let fitted = Model::build(&noisy);
+        assert_relative_eq!(fitted.lambda, model.lambda, max_relative = 0.05);
+
+        let noisy = model.generate(&concurrency, NoiseSpec::Gaussian { sd: 100.0 }, &mut rng);
+        let sd = (noisy
+            .iter()
+            .map(|m| (m.x - model.throughput_at_concurrency_f64(m.n)).powi(2))
+            .sum::<f64>()
+            / noisy.len() as f64)
+            .sqrt();
+        assert!(sd > 50.0 && sd < 200.0, "{}", sd);
+    }
+
+    #[test]
+    fn with_noise() {
+        let dataset: Dataset = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let mut rng = StdRng::seed_from_u64(0xDEADBEEF);
+
+        let exact = dataset.with_noise(NoiseSpec::None, &mut rng);
+        for (a, b) in exact.iter().zip(dataset.iter()) {
+            assert_eq!((a.n, a.x, a.r), (b.n, b.x, b.r));
+        }
+
+        let noisy = dataset.with_noise(NoiseSpec::Proportional { cv: 0.01 }, &mut rng);
+        assert_eq!(noisy.len(), dataset.len());
+        let errors: Vec<f64> =
+            noisy.iter().zip(dataset.iter()).map(|(a, b)| a.x / b.x - 1.0).collect();
+        let sd = (errors.iter().map(|e| e * e).sum::<f64>() / errors.len() as f64).sqrt();
+        assert!(sd > 0.005 && sd < 0.02, "{}", sd);
+        for (a, b) in noisy.iter().zip(dataset.iter()) {
+            assert_eq!(a.n, b.n);
+            assert_relative_eq!(a.n, a.x * a.r);
+        }
+
+        let fitted = Model::build(&noisy);
+        assert_relative_eq!(fitted.lambda, 995.6486, max_relative = 0.05);
+    }
+}
@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+use crate::{Measurement, ScalabilityModel};
+
+/// A model of a system which obeys the Gustafson–Barsis Law, for weak-scaling analyses in which
+/// the size of the problem grows with the number of workers.
+///
+/// The scaled speedup with `N` workers is `S(N) = N - α(N-1)`, where α is the fraction of the work
+/// which is serial, and so the rate at which work is done is `X(N) = λS(N)`. Unlike Amdahl's Law,
+/// this grows without bound.
+///
+/// ```
+/// use std::time::Duration;
+/// use usl::GustafsonModel;
+///
+/// // Each run processes a problem sized in proportion to the number of workers.
+/// let runs = [
+///     (1, 1_000.0, Duration::from_secs(10)),
+///     (2, 2_000.0, Duration::from_secs_f64(10.5)),
+///     (4, 4_000.0, Duration::from_secs_f64(11.4)),
+///     (8, 8_000.0, Duration::from_secs_f64(12.3)),
+/// ];
+/// let model = GustafsonModel::build_from_runs(&runs);
+/// println!("serial fraction: {}", model.serial_fraction);
+/// ```
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GustafsonModel {
+    /// The fraction of work which is serial, α.
+    pub serial_fraction: f64,
+    /// The rate at which a single worker does work, λ.
+    pub lambda: f64,
+}
+
+impl GustafsonModel {
+    /// Build a model from measurements of the rate at which work is done by different numbers of
+    /// workers.
+    ///
+    /// Because the scaled speedup is linear in `N`, the model is fitted by ordinary least squares.
+    /// Panics if there are fewer than two distinct numbers of workers.
+    #[must_use]
+    pub fn build(measurements: &[Measurement]) -> GustafsonModel {
+        let n = measurements.len() as f64;
+        let mean_n = measurements.iter().map(|m| m.n).sum::<f64>() / n;
+        let mean_x = measurements.iter().map(|m| m.x).sum::<f64>() / n;
+        let ss_n: f64 = measurements.iter().map(|m| (m.n - mean_n).powi(2)).sum();
+        assert!(ss_n > 0.0, "must have at least two distinct numbers of workers");
+
+        // X(N) = λα + λ(1-α)N, so the intercept and slope give λ and α.
+        let slope =
+            measurements.iter().map(|m| (m.n - mean_n) * (m.x - mean_x)).sum::<f64>() / ss_n;
+        let intercept = mean_x - slope * mean_n;
+        let lambda = intercept + slope;
+        GustafsonModel { serial_fraction: intercept / lambda, lambda }
+    }
+
+    /// Build a model from runs of a workload, each given as the number of workers, the amount of
+    /// work done, and the time it took. The amount of work may vary from run to run.
+    #[must_use]
+    pub fn build_from_runs(runs: &[(u32, f64, Duration)]) -> GustafsonModel {
+        let measurements: Vec<Measurement> = runs
+            .iter()
+            .map(|&(n, work, elapsed)| {
+                Measurement::concurrency_and_throughput(n, work / elapsed.as_secs_f64())
+            })
+            .collect();
+        GustafsonModel::build(&measurements)
+    }
+
+    /// Calculate the scaled speedup with a given number of workers, `S(N) = N - α(N-1)`.
+    #[must_use]
+    pub fn scaled_speedup_at_concurrency(&self, n: u32) -> f64 {
+        let n: f64 = n.into();
+        n - self.serial_fraction * (n - 1.0)
+    }
+
+    /// Calculate the expected rate at which work is done with a given number of workers, `X(N)`.
+    #[must_use]
+    pub fn throughput_at_concurrency(&self, n: u32) -> f64 {
+        self.throughput_at_concurrency_f64(n.into())
+    }
+
+    /// Calculate the expected rate at which work is done given a possibly fractional average
+    /// number of workers, `X(N)`.
+    #[must_use]
+    pub fn throughput_at_concurrency_f64(&self, n: f64) -> f64 {
+        self.lambda * (n - self.serial_fraction * (n - 1.0))
+    }
+}
+
+impl ScalabilityModel for GustafsonModel {
+    fn throughput_at_concurrency(&self, n: u32) -> f64 {
+        GustafsonModel::throughput_at_concurrency(self, n)
+    }
+
+    fn throughput_at_concurrency_f64(&self, n: f64) -> f64 {
+        GustafsonModel::throughput_at_concurrency_f64(self, n)
+    }
+
+    fn parameters(&self) -> usize {
+        2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn build() {
+        let expected = GustafsonModel { serial_fraction: 0.2, lambda: 50.0 };
+        let measurements: Vec<Measurement> = [1, 2, 4, 8, 16, 32]
+            .iter()
+            .map(|&n| {
+                Measurement::concurrency_and_throughput(n, expected.throughput_at_concurrency(n))
+            })
+            .collect();
+        let model = GustafsonModel::build(&measurements);
+        assert_relative_eq!(model.serial_fraction, 0.2, max_relative = 1e-9);
+        assert_relative_eq!(model.lambda, 50.0, max_relative = 1e-9);
+        assert_relative_eq!(model.scaled_speedup_at_concurrency(1), 1.0);
+        assert_relative_eq!(model.scaled_speedup_at_concurrency(11), 9.0, max_relative = 1e-9);
+        assert_relative_eq!(model.throughput_at_concurrency_f64(1.5), 70.0, max_relative = 1e-9);
+    }
+
+    #[test]
+    fn build_from_runs() {
+        // The work per run grows with the number of workers, and the time each run takes grows
+        // with the serial fraction of the work.
+        let expected = GustafsonModel { serial_fraction: 0.1, lambda: 100.0 };
+        let runs: Vec<(u32, f64, Duration)> = [1, 2, 4, 8, 16]
+            .iter()
+            .map(|&n| {
+                let work = 1_000.0 * f64::from(n);
+                (n, work, Duration::from_secs_f64(work / expected.throughput_at_concurrency(n)))
+            })
+            .collect();
+        let model = GustafsonModel::build_from_runs(&runs);
+        assert_relative_eq!(model.serial_fraction, 0.1, max_relative = 1e-6);
+        assert_relative_eq!(model.lambda, 100.0, max_relative = 1e-6);
+    }
+}
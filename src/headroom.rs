@@ -0,0 +1,81 @@
+use crate::Model;
+
+/// A system's current load, given as either its concurrency or its throughput.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Load {
+    /// The average number of concurrent events.
+    Concurrency(f64),
+    /// The throughput, in events/sec.
+    Throughput(f64),
+}
+
+/// The remaining capacity of a system at a given load, via [Model::headroom].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Headroom {
+    /// The additional throughput the system can handle before reaching [Model::max_throughput].
+    pub throughput: f64,
+    /// The additional throughput as a fraction of [Model::max_throughput].
+    pub fraction: f64,
+    /// The additional concurrency the system can absorb before reaching
+    /// [Model::max_concurrency]. This is infinite if the model has no finite peak.
+    pub concurrency: f64,
+}
+
+impl Model {
+    /// Calculate the remaining capacity of the system given its current load.
+    ///
+    /// A throughput load is assumed to be on the ascending branch of the throughput curve. Loads
+    /// at or beyond the peak have no headroom.
+    #[must_use]
+    pub fn headroom(&self, load: Load) -> Headroom {
+        let max_throughput = self.max_throughput();
+        let max_concurrency = self.checked_max_concurrency().map_or(f64::INFINITY, f64::from);
+        let (n, x) = match load {
+            Load::Concurrency(n) => (n, self.throughput_at_concurrency_f64(n)),
+            Load::Throughput(x) => {
+                (self.concurrency_for_throughput(x).unwrap_or(max_concurrency), x)
+            }
+        };
+        let throughput = if n < max_concurrency { (max_throughput - x).max(0.0) } else { 0.0 };
+        Headroom {
+            throughput,
+            fraction: throughput / max_throughput,
+            concurrency: (max_concurrency - n).max(0.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn headroom() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        let x = model.throughput_at_concurrency(20);
+
+        let headroom = model.headroom(Load::Concurrency(20.0));
+        assert_relative_eq!(headroom.throughput, model.max_throughput() - x);
+        assert_relative_eq!(headroom.fraction, 1.0 - x / model.max_throughput());
+        assert_relative_eq!(headroom.concurrency, 15.0);
+
+        let by_throughput = model.headroom(Load::Throughput(x));
+        assert_relative_eq!(by_throughput.throughput, headroom.throughput);
+        assert_relative_eq!(by_throughput.concurrency, 15.0, max_relative = 1e-6);
+
+        let saturated = model.headroom(Load::Concurrency(50.0));
+        assert_relative_eq!(saturated.throughput, 0.0);
+        assert_relative_eq!(saturated.concurrency, 0.0);
+        let saturated = model.headroom(Load::Throughput(20_000.0));
+        assert_relative_eq!(saturated.fraction, 0.0);
+        assert_relative_eq!(saturated.concurrency, 0.0);
+
+        let limitless = Model { sigma: 0.1, kappa: 0.0, lambda: 100.0 };
+        assert_eq!(limitless.headroom(Load::Concurrency(20.0)).concurrency, f64::INFINITY);
+    }
+}
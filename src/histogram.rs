@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+use hdrhistogram::{Counter, Histogram};
+
+use crate::{LatencyProfile, LatencyStatistic, Load, Measurement};
+
+impl Measurement {
+    /// Create a measurement from a histogram of latencies recorded in units of `unit` (e.g.
+    /// `Duration::from_micros(1)`), plus either the system's observed concurrency or its observed
+    /// throughput. The other is derived via Little's Law.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use hdrhistogram::Histogram;
+    /// use usl::{LatencyStatistic, Load, Measurement};
+    ///
+    /// let mut histogram = Histogram::<u64>::new(3).unwrap();
+    /// histogram.record_n(20_000, 100).unwrap();
+    /// let m = Measurement::from_histogram(
+    ///     Load::Concurrency(16.0),
+    ///     &histogram,
+    ///     Duration::from_micros(1),
+    ///     LatencyStatistic::Mean,
+    /// );
+    /// ```
+    #[must_use]
+    pub fn from_histogram<T: Counter>(
+        load: Load,
+        histogram: &Histogram<T>,
+        unit: Duration,
+        statistic: LatencyStatistic,
+    ) -> Measurement {
+        let value = match statistic {
+            LatencyStatistic::Mean => histogram.mean(),
+            LatencyStatistic::Quantile(q) => histogram.value_at_quantile(q) as f64,
+        };
+        let r = value * unit.as_secs_f64();
+        match load {
+            Load::Concurrency(n) => Measurement { n, x: n / r, r },
+            Load::Throughput(x) => Measurement { n: x * r, x, r },
+        }
+    }
+}
+
+impl LatencyProfile {
+    /// Create a latency profile from a histogram of latencies recorded in units of `unit` (e.g.
+    /// `Duration::from_micros(1)`).
+    #[must_use]
+    pub fn from_histogram<T: Counter>(histogram: &Histogram<T>, unit: Duration) -> LatencyProfile {
+        let unit = unit.as_secs_f64();
+        let quantile = |q| histogram.value_at_quantile(q) as f64 * unit;
+        LatencyProfile {
+            mean: histogram.mean() * unit,
+            p50: quantile(0.5),
+            p90: quantile(0.9),
+            p99: quantile(0.99),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn from_histogram() {
+        let mut histogram = Histogram::<u64>::new(3).unwrap();
+        histogram.record_n(10, 90).unwrap();
+        histogram.record_n(100, 10).unwrap();
+        let unit = Duration::from_millis(1);
+
+        let m = Measurement::from_histogram(
+            Load::Concurrency(19.0),
+            &histogram,
+            unit,
+            LatencyStatistic::Mean,
+        );
+        assert_relative_eq!(m.r, 0.019, max_relative = 0.001);
+        assert_relative_eq!(m.x, 1000.0, max_relative = 0.001);
+
+        let m = Measurement::from_histogram(
+            Load::Throughput(500.0),
+            &histogram,
+            unit,
+            LatencyStatistic::Quantile(0.99),
+        );
+        assert_relative_eq!(m.r, 0.1, max_relative = 0.001);
+        assert_relative_eq!(m.n, 50.0, max_relative = 0.001);
+    }
+
+    #[test]
+    fn latency_profile_from_histogram() {
+        let mut histogram = Histogram::<u64>::new(3).unwrap();
+        histogram.record_n(10, 90).unwrap();
+        histogram.record_n(100, 10).unwrap();
+
+        let profile = LatencyProfile::from_histogram(&histogram, Duration::from_millis(1));
+        assert_relative_eq!(profile.mean, 0.019, max_relative = 0.001);
+        assert_relative_eq!(profile.p50, 0.010, max_relative = 0.001);
+        assert_relative_eq!(profile.p90, 0.010, max_relative = 0.001);
+        assert_relative_eq!(profile.p99, 0.100, max_relative = 0.001);
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{Measurement, Model, ModelFitter, StandardErrors};
+
+/// A set of leave-one-out models used to estimate the bias and variance of a fit, via
+/// [Model::jackknife].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Jackknife {
+    /// The model fitted to all the measurements.
+    pub model: Model,
+    /// The models fitted with each measurement left out in turn, such that `models[i]` was fitted
+    /// without the `i`th measurement.
+    pub models: Vec<Model>,
+}
+
+impl Jackknife {
+    /// Estimate the bias of an arbitrary function of the model, e.g. [Model::max_throughput].
+    #[must_use]
+    pub fn bias(&self, f: impl Fn(&Model) -> f64) -> f64 {
+        let n = self.models.len() as f64;
+        let mean = self.models.iter().map(&f).sum::<f64>() / n;
+        (n - 1.0) * (mean - f(&self.model))
+    }
+
+    /// Estimate the standard error of an arbitrary function of the model.
+    #[must_use]
+    pub fn standard_error(&self, f: impl Fn(&Model) -> f64) -> f64 {
+        let n = self.models.len() as f64;
+        let mean = self.models.iter().map(&f).sum::<f64>() / n;
+        ((n - 1.0) / n * self.models.iter().map(|m| (f(m) - mean).powi(2)).sum::<f64>()).sqrt()
+    }
+
+    /// Return the model with each parameter's estimated bias subtracted.
+    #[must_use]
+    pub fn corrected(&self) -> Model {
+        Model {
+            sigma: self.model.sigma - self.bias(|m| m.sigma),
+            kappa: self.model.kappa - self.bias(|m| m.kappa),
+            lambda: self.model.lambda - self.bias(|m| m.lambda),
+        }
+    }
+
+    /// Estimate the standard errors and covariance of the model's parameters.
+    #[must_use]
+    pub fn standard_errors(&self) -> StandardErrors {
+        let n = self.models.len() as f64;
+        let params: Vec<[f64; 3]> =
+            self.models.iter().map(|m| [m.sigma, m.kappa, m.lambda]).collect();
+        let mut mean = [0.0; 3];
+        for p in &params {
+            for (m, v) in mean.iter_mut().zip(p) {
+                *m += v / n;
+            }
+        }
+
+        let mut covariance = [[0.0; 3]; 3];
+        for p in &params {
+            for (i, row) in covariance.iter_mut().enumerate() {
+                for (j, c) in row.iter_mut().enumerate() {
+                    *c += (n - 1.0) / n * (p[i] - mean[i]) * (p[j] - mean[j]);
+                }
+            }
+        }
+        StandardErrors {
+            sigma: covariance[0][0].sqrt(),
+            kappa: covariance[1][1].sqrt(),
+            lambda: covariance[2][2].sqrt(),
+            covariance,
+        }
+    }
+}
+
+impl Model {
+    /// Fit a model to the given measurements along with a model for each subset with one
+    /// measurement left out.
+    ///
+    /// A cheaper alternative to [Model::bootstrap] which is also well-behaved for small datasets,
+    /// where bootstrap resamples often contain too few distinct measurements to fit.
+    ///
+    /// ```
+    /// use usl::{Jackknife, Measurement, Model};
+    ///
+    /// let measurements: Vec<Measurement> =
+    ///     [(1, 65.0), (18, 996.0), (36, 1652.0), (72, 1853.0), (108, 1829.0), (144, 1775.0), (216, 1680.0)]
+    ///         .iter()
+    ///         .map(|&m| m.into())
+    ///         .collect();
+    /// let jackknife: Jackknife = Model::jackknife(&measurements);
+    /// println!("X{{max}} ± {}", jackknife.standard_error(|m| m.max_throughput()));
+    /// ```
+    #[must_use]
+    pub fn jackknife(measurements: &[Measurement]) -> Jackknife {
+        let model = Model::build(measurements);
+        let models = (0..measurements.len())
+            .map(|i| {
+                let mut subset = measurements.to_vec();
+                subset.remove(i);
+                match Model::try_fit(&ModelFitter::new(&subset), None) {
+                    Ok(model) => model,
+                    Err(err) => panic!("{}", err),
+                }
+            })
+            .collect();
+        Jackknife { model, models }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+    use crate::MIN_MEASUREMENTS;
+
+    #[test]
+    fn jackknife() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let jackknife = Model::jackknife(&measurements);
+
+        assert_eq!(jackknife.models.len(), 32);
+        assert_relative_eq!(jackknife.model.kappa, 7.690945e-4, max_relative = 0.00001);
+
+        let errors = jackknife.standard_errors();
+        assert_relative_eq!(errors.kappa, jackknife.standard_error(|m| m.kappa));
+        assert!(errors.kappa > 0.0 && errors.kappa < jackknife.model.kappa);
+
+        let corrected = jackknife.corrected();
+        assert_relative_eq!(corrected.kappa, jackknife.model.kappa, max_relative = 0.1);
+    }
+
+    #[test]
+    fn jackknife_minimal() {
+        let measurements: Vec<Measurement> =
+            MEASUREMENTS[..MIN_MEASUREMENTS].iter().map(|&m| m.into()).collect();
+        let jackknife = Model::jackknife(&measurements);
+
+        assert_eq!(jackknife.models.len(), MIN_MEASUREMENTS);
+        assert!(jackknife.standard_error(|m| m.lambda).is_finite());
+    }
+}
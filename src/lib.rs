@@ -31,11 +31,120 @@
     clippy::needless_borrow
 )]
 
+use std::f64::consts::PI;
 use std::iter::FromIterator;
+use std::ops::{RangeFrom, RangeInclusive};
 use std::time::Duration;
 
 use approx::relative_eq;
-use rmpfit::{MPFitter, MPResult};
+use rand::Rng;
+use rmpfit::{MPFitter, MPPar};
+
+pub use amdahl::AmdahlModel;
+#[cfg(feature = "bayes")]
+pub use bayes::{McmcOptions, Posterior, Prior};
+pub use bootstrap::Bootstrap;
+pub use bucket::Bucket;
+pub use builder::{Aggregation, ModelBuilder};
+pub use cluster::Cluster;
+pub use dataset::{Dataset, Level, Metadata, Statistics};
+#[cfg(feature = "csv")]
+pub use dataset_csv::{Column, CsvError, CsvOptions, MeasurementKind};
+#[cfg(feature = "json")]
+pub use dataset_json::{JsonLines, JsonLinesError};
+pub use dataset_profile::{DatasetProfile, Gap, MonotonicityBreak};
+pub use diff::{Change, ModelDiff, Verdict};
+pub use downsample::Downsample;
+pub use ensemble::{Ensemble, Spread};
+pub use error::{BuildError, Infeasible, InverseError, Problem, ValidationError};
+pub use evaluate::{EvalReport, PointError};
+pub use extended::ExtendedModel;
+pub use fitter::{
+    FitError, FitReport, Fitter, Lma, NelderMead, Objective, StandardErrors, Termination,
+};
+pub use generate::NoiseSpec;
+pub use gustafson::GustafsonModel;
+pub use headroom::{Headroom, Load};
+pub use jackknife::Jackknife;
+pub use marginal::MarginalSummary;
+pub use multifactor::{MultiFactorModel, Observation, Varying};
+pub use multistart::Multistart;
+pub use online::OnlineModel;
+pub use penalty::{LatencyComponents, PenaltyBreakdown};
+pub use piecewise::{Piece, Piecewise};
+pub use predict::{BandedPrediction, Prediction, Uncertainty};
+pub use profile::{LatencyProfile, LatencyStatistic, ProfiledMeasurement};
+pub use quality::FitQuality;
+pub use ransac::{Ransac, RansacOptions};
+pub use records::RequestRecord;
+pub use runs::{Runs, TaggedMeasurement};
+pub use sensitivity::Sensitivity;
+pub use sla::{Constraint, Sla, SlaVerdict};
+pub use split::SplitStrategy;
+pub use summary::{Classification, ModelSummary};
+pub use trim::{TrimPolicy, Trimmed, TrimmedPoint};
+pub use units::{LatencyUnit, RateUnit};
+pub use warning::FitWarning;
+pub use what_if::WhatIf;
+pub use window::{Window, WindowedModel};
+
+use fitter::LmaObjective;
+
+mod amdahl;
+#[cfg(feature = "bayes")]
+mod bayes;
+mod bootstrap;
+mod bucket;
+mod builder;
+pub mod capacity;
+mod cluster;
+pub mod compare;
+mod dataset;
+#[cfg(feature = "arrow")]
+mod dataset_arrow;
+#[cfg(feature = "csv")]
+mod dataset_csv;
+#[cfg(feature = "json")]
+mod dataset_json;
+mod dataset_profile;
+mod diff;
+mod downsample;
+pub mod drift;
+mod ensemble;
+mod error;
+mod evaluate;
+mod extended;
+mod fitter;
+mod generate;
+mod gustafson;
+mod headroom;
+#[cfg(feature = "hdrhistogram")]
+mod histogram;
+mod jackknife;
+mod marginal;
+mod multifactor;
+mod multistart;
+mod online;
+pub mod outliers;
+mod penalty;
+mod piecewise;
+mod predict;
+mod profile;
+mod quality;
+mod ransac;
+mod records;
+mod runs;
+mod sensitivity;
+pub mod sim;
+mod sla;
+mod split;
+mod summary;
+mod trim;
+mod units;
+pub mod validate;
+mod warning;
+mod what_if;
+mod window;
 
 /// A simultaneous measurement of at least two of the parameters of Little's Law: concurrency,
 /// throughput, and latency. The third parameter is inferred from the other two.
@@ -54,8 +163,11 @@ use rmpfit::{MPFitter, MPResult};
 /// let m: Measurement = (Duration::from_millis(200), 30).into();
 /// let m: Measurement = (1000.0, Duration::from_millis(200)).into();
 /// let m: Measurement = (Duration::from_millis(200), 1000.0).into();
+/// let m: Measurement = (30usize, 1000.0).into();
+/// let m: Measurement = (30f32, 1000f32).into();
 /// ```
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Measurement {
     /// The average number of concurrent events.
     pub n: f64,
@@ -90,6 +202,91 @@ impl Measurement {
         let r = r.as_secs_f64();
         Measurement { n: x * r, x, r } // L=λW, W, λ
     }
+
+    /// Create a measurement as with [Measurement::concurrency_and_latency], returning an error
+    /// instead of a measurement with non-positive or non-finite values.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use usl::{Measurement, Problem};
+    ///
+    /// let err = Measurement::try_concurrency_and_latency(0, Duration::from_millis(10));
+    /// assert_eq!(err.unwrap_err(), Problem::NonPositiveConcurrency);
+    /// ```
+    pub fn try_concurrency_and_latency(n: u32, r: Duration) -> Result<Measurement, Problem> {
+        if n == 0 {
+            Err(Problem::NonPositiveConcurrency)
+        } else if r.is_zero() {
+            Err(Problem::NonPositiveLatency)
+        } else {
+            checked(Measurement::concurrency_and_latency(n, r))
+        }
+    }
+
+    /// Create a measurement as with [Measurement::concurrency_and_throughput], returning an error
+    /// instead of a measurement with non-positive or non-finite values.
+    ///
+    /// ```
+    /// use usl::{Measurement, Problem};
+    ///
+    /// let err = Measurement::try_concurrency_and_throughput(5, 0.0);
+    /// assert_eq!(err.unwrap_err(), Problem::NonPositiveThroughput);
+    /// ```
+    pub fn try_concurrency_and_throughput(n: u32, x: f64) -> Result<Measurement, Problem> {
+        if n == 0 {
+            Err(Problem::NonPositiveConcurrency)
+        } else {
+            checked_throughput(x)?;
+            checked(Measurement::concurrency_and_throughput(n, x))
+        }
+    }
+
+    /// Create a measurement as with [Measurement::throughput_and_latency], returning an error
+    /// instead of a measurement with non-positive or non-finite values.
+    pub fn try_throughput_and_latency(x: f64, r: Duration) -> Result<Measurement, Problem> {
+        checked_throughput(x)?;
+        if r.is_zero() {
+            Err(Problem::NonPositiveLatency)
+        } else {
+            checked(Measurement::throughput_and_latency(x, r))
+        }
+    }
+}
+
+/// Check a throughput before anything is derived from it, so that e.g. zero throughput isn't
+/// reported as the infinite latency it implies.
+fn checked_throughput(x: f64) -> Result<(), Problem> {
+    if x <= 0.0 {
+        Err(Problem::NonPositiveThroughput)
+    } else if !x.is_finite() {
+        Err(Problem::NonFinite)
+    } else {
+        Ok(())
+    }
+}
+
+/// Check the values derived from valid arguments, which may still overflow.
+fn checked(m: Measurement) -> Result<Measurement, Problem> {
+    Problem::of(&m).map_or(Ok(m), Err)
+}
+
+/// A [Measurement] taken at a particular time.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimedMeasurement {
+    /// The time at which the measurement was taken, relative to an arbitrary epoch (e.g. the Unix
+    /// epoch).
+    pub time: Duration,
+    /// The measurement.
+    pub measurement: Measurement,
+}
+
+impl TimedMeasurement {
+    /// Create a measurement taken at the given time.
+    #[must_use]
+    pub const fn new(time: Duration, measurement: Measurement) -> TimedMeasurement {
+        TimedMeasurement { time, measurement }
+    }
 }
 
 macro_rules! from_tuple {
@@ -111,6 +308,23 @@ macro_rules! from_tuple {
 from_tuple!(u32, f64, Measurement::concurrency_and_throughput);
 from_tuple!(u32, Duration, Measurement::concurrency_and_latency);
 from_tuple!(f64, Duration, Measurement::throughput_and_latency);
+from_tuple!(u16, f64, |n, x| Measurement::concurrency_and_throughput(u32::from(n), x));
+// Unsuffixed integer literals fall back to i32 when more than one integer type would do, so this
+// keeps e.g. `(30, 1000.0).into()` compiling.
+from_tuple!(i32, f64, |n, x| concurrency_and_throughput_f64(f64::from(n), x));
+from_tuple!(u64, f64, |n, x| concurrency_and_throughput_f64(n as f64, x));
+from_tuple!(usize, f64, |n, x| concurrency_and_throughput_f64(n as f64, x));
+
+/// Converts a `(concurrency, throughput)` pair.
+impl From<(f32, f32)> for Measurement {
+    fn from(v: (f32, f32)) -> Self {
+        concurrency_and_throughput_f64(v.0.into(), v.1.into())
+    }
+}
+
+const fn concurrency_and_throughput_f64(n: f64, x: f64) -> Measurement {
+    Measurement { n, x, r: n / x } // L, λ, W=L/λ
+}
 
 /// A Universal Scalability Law model.
 ///
@@ -128,6 +342,7 @@ from_tuple!(f64, Duration, Measurement::throughput_and_latency);
 /// ].iter().collect();
 /// ```
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Model {
     /// The model's coefficient of contention, σ.
     pub sigma: f64,
@@ -137,9 +352,116 @@ pub struct Model {
     pub lambda: f64,
 }
 
+/// The quantity whose residuals are minimized when fitting a model.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FitTarget {
+    /// Minimize the residuals of throughput at each measured concurrency level, `X(N)`.
+    #[default]
+    Throughput,
+    /// Minimize the residuals of latency at each measured concurrency level, `R(N)`. This gives
+    /// better parameters for datasets collected as concurrency and latency.
+    Latency,
+    /// Minimize the residuals of latency at each measured throughput, `R(X)`. This avoids deriving
+    /// concurrency from throughput and latency, which amplifies noise in datasets collected from
+    /// open-loop load generators as throughput and latency. Latency is very sensitive to the
+    /// parameters near peak throughput, so this is best combined with [FitOptions::grid_search].
+    LatencyAtThroughput,
+}
+
+/// The way in which the difference between observed and predicted values is measured when fitting
+/// a model.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Residual {
+    /// The absolute error, `y - ŷ`. Measurements with large values dominate the fit.
+    #[default]
+    Absolute,
+    /// The relative error, `(y - ŷ)/y`. Measurements influence the fit in proportion to their
+    /// relative, rather than absolute, error.
+    Relative,
+    /// The log-space error, `ln(y) - ln(ŷ)`. Similar to [Residual::Relative], but symmetric for
+    /// over- and under-predictions.
+    Log,
+}
+
+/// Options for building a model via [Model::build_with_options].
+#[derive(Debug, Default, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FitOptions {
+    /// The quantity whose residuals are minimized.
+    pub target: FitTarget,
+    /// The way residuals are measured.
+    pub residual: Residual,
+    /// Whether to search a coarse grid of σ and κ values for the best starting point before
+    /// refining it. This costs extra evaluations, but helps avoid converging to degenerate
+    /// parameters for datasets with retrograde throughput.
+    pub grid_search: bool,
+}
+
+/// A model which predicts a system's throughput from its concurrency.
+///
+/// Implemented by [Model] and by the other scalability models in this crate, allowing them to be
+/// compared with one another (e.g. via [compare::information_criteria]).
+pub trait ScalabilityModel {
+    /// Calculate the expected throughput given a number of concurrent events, `X(N)`.
+    fn throughput_at_concurrency(&self, n: u32) -> f64;
+
+    /// Calculate the expected throughput given a possibly fractional average number of concurrent
+    /// events, `X(N)`.
+    fn throughput_at_concurrency_f64(&self, n: f64) -> f64;
+
+    /// The number of parameters fitted to produce the model. Implementations which don't record how
+    /// the model was fitted may infer this, e.g. from which of its coefficients are non-zero.
+    fn parameters(&self) -> usize;
+}
+
+impl ScalabilityModel for Model {
+    fn throughput_at_concurrency(&self, n: u32) -> f64 {
+        Model::throughput_at_concurrency(self, n)
+    }
+
+    fn throughput_at_concurrency_f64(&self, n: f64) -> f64 {
+        Model::throughput_at_concurrency_f64(self, n)
+    }
+
+    /// The number of non-zero coefficients of the model. Models built with coefficients pinned at
+    /// zero, e.g. via [Model::build_amdahl], have fewer parameters.
+    ///
+    /// A fitted coefficient which ends up at exactly zero, e.g. by reaching a bound of
+    /// [Model::build_constrained], is indistinguishable from a pinned one. To count the parameters
+    /// which were actually fitted, use the model and [FitReport] from e.g.
+    /// [Model::build_with_report] instead.
+    fn parameters(&self) -> usize {
+        1 + usize::from(self.sigma != 0.0) + usize::from(self.kappa != 0.0)
+    }
+}
+
+impl ScalabilityModel for (Model, FitReport) {
+    fn throughput_at_concurrency(&self, n: u32) -> f64 {
+        self.0.throughput_at_concurrency(n)
+    }
+
+    fn throughput_at_concurrency_f64(&self, n: f64) -> f64 {
+        self.0.throughput_at_concurrency_f64(n)
+    }
+
+    /// The number of free parameters of the fit, per [FitReport::free_parameters].
+    fn parameters(&self) -> usize {
+        self.1.free_parameters
+    }
+}
+
 /// The minimum number of measurements required to build a model.
 pub const MIN_MEASUREMENTS: usize = 6;
 
+/// The tuning constant for the Huber loss used by [Model::build_robust], in units of the
+/// residuals' robust standard deviation. The conventional value of 1.345 gives 95% efficiency for
+/// normally distributed residuals.
+pub const HUBER_K: f64 = 1.345;
+
+const MAX_IRLS_ITERATIONS: usize = 50;
+
 impl Model {
     /// Build a model whose parameters are generated from the given measurements.
     ///
@@ -148,25 +470,305 @@ impl Model {
     /// and σ are the parameters of the returned model.
     #[must_use]
     pub fn build(measurements: &[Measurement]) -> Model {
+        Model::fit(ModelFitter::new(measurements), None)
+    }
+
+    /// Build a model as with [Model::build], returning an error instead of panicking if the
+    /// measurements are invalid or the model cannot be fitted to them.
+    ///
+    /// The measurements are first checked via [ValidationError::check], so that bad inputs (e.g.
+    /// unparseable CSV rows) are reported by index instead of producing NaN parameters.
+    pub fn try_build(measurements: &[Measurement]) -> Result<Model, BuildError> {
+        ValidationError::check(measurements)?;
+        Ok(Model::try_fit(&ModelFitter::new(measurements), None)?)
+    }
+
+    /// Build a model whose parameters are constrained to physically meaningful values.
+    ///
+    /// Like [Model::build], but bounds the regression so that `σ ≥ 0`, `κ ≥ 0`, and `λ > 0`. An
+    /// unconstrained fit of noisy or superlinear data can produce negative coefficients, which
+    /// break derived quantities like [Model::max_concurrency].
+    #[must_use]
+    pub fn build_constrained(measurements: &[Measurement]) -> Model {
+        let bounds = [bounded_below(0.0), bounded_below(0.0), bounded_below(f64::MIN_POSITIVE)];
+        Model::fit(ModelFitter::new(measurements), Some(&bounds))
+    }
+
+    /// Build a model of a system with no coherency penalty, i.e. one which obeys Amdahl's Law.
+    ///
+    /// Like [Model::build], but pins κ at exactly zero and fits only σ and λ. Comparing the result
+    /// with a full fit shows whether the coherency term actually explains anything in the data.
+    #[must_use]
+    pub fn build_amdahl(measurements: &[Measurement]) -> Model {
+        let constraints = [MPPar::default(), pinned(), MPPar::default()];
+        Model::fit(ModelFitter::new(measurements), Some(&constraints))
+    }
+
+    /// Build a model of a system with no contention penalty, i.e. one limited only by coherency.
+    ///
+    /// Like [Model::build], but pins σ at exactly zero and fits only κ and λ.
+    #[must_use]
+    pub fn build_contention_free(measurements: &[Measurement]) -> Model {
+        let constraints = [pinned(), MPPar::default(), MPPar::default()];
+        Model::fit(ModelFitter::new(measurements), Some(&constraints))
+    }
+
+    /// Build a model of a linearly scalable system.
+    ///
+    /// Like [Model::build], but pins both σ and κ at exactly zero and fits only λ. This is the
+    /// simplest model nested within the USL, and is useful as a baseline for comparison.
+    #[must_use]
+    pub fn build_linear(measurements: &[Measurement]) -> Model {
+        let constraints = [pinned(), pinned(), MPPar::default()];
+        Model::fit(ModelFitter::new(measurements), Some(&constraints))
+    }
+
+    /// Build a model from measurements of varying reliability.
+    ///
+    /// Like [Model::build], but scales each measurement's residual by the square root of its
+    /// weight, so a measurement with twice the weight has twice the influence on the fit. Weights
+    /// are typically the inverse of each measurement's variance, and must be non-negative.
+    #[must_use]
+    pub fn build_weighted(measurements: &[(Measurement, f64)]) -> Model {
+        Model::fit(ModelFitter::weighted(measurements), None)
+    }
+
+    /// Build a model which is resistant to a small number of outlying measurements.
+    ///
+    /// Fits the model using iteratively reweighted least squares with a Huber loss: measurements
+    /// whose residuals are within [HUBER_K] robust standard deviations of the fit are treated as
+    /// in [Model::build], while those further out are progressively down-weighted.
+    #[must_use]
+    pub fn build_robust(measurements: &[Measurement]) -> Model {
+        let mut model = Model::build(measurements);
+        for _ in 0..MAX_IRLS_ITERATIONS {
+            let residuals: Vec<f64> = measurements
+                .iter()
+                .map(|m| m.x - model.throughput_at_concurrency_f64(m.n))
+                .collect();
+
+            let scale = robust_scale(&residuals);
+            if scale == 0.0 {
+                break;
+            }
+
+            let k = HUBER_K * scale;
+            let weighted: Vec<(Measurement, f64)> = measurements
+                .iter()
+                .zip(residuals.iter())
+                .map(|(&m, r)| (m, if r.abs() <= k { 1.0 } else { k / r.abs() }))
+                .collect();
+            let prev = model;
+            model = Model::build_weighted(&weighted);
+            if relative_eq!(model.sigma, prev.sigma)
+                && relative_eq!(model.kappa, prev.kappa)
+                && relative_eq!(model.lambda, prev.lambda)
+            {
+                break;
+            }
+        }
+        model
+    }
+
+    /// Build a model which predicts a quantile of latency, rather than its mean.
+    ///
+    /// Each measurement's latency should be the given quantile of the latencies observed at that
+    /// concurrency level, e.g. `0.99` for p99 latencies. The model is fitted to latency using
+    /// iteratively reweighted least squares with a pinball loss, which penalizes under-predictions
+    /// by `quantile` and over-predictions by `1 - quantile`. The resulting model's
+    /// [Model::latency_at_concurrency] predicts tail latency, so values derived from it (e.g.
+    /// [Model::concurrency_at_latency]) describe tail behavior.
+    ///
+    /// Panics if `quantile` is not strictly between 0 and 1.
+    #[must_use]
+    pub fn build_quantile(measurements: &[Measurement], quantile: f64) -> Model {
+        assert!(quantile > 0.0 && quantile < 1.0, "quantile must be between 0 and 1");
+        let options = FitOptions { target: FitTarget::Latency, ..Default::default() };
+        let mut model = Model::build_with_options(measurements, &options);
+        for _ in 0..MAX_IRLS_ITERATIONS {
+            // Weighting each squared residual by the pinball loss divided by the residual's
+            // magnitude makes the weighted sum of squares equal to the total pinball loss.
+            let weighted: Vec<(Measurement, f64)> = measurements
+                .iter()
+                .map(|&m| {
+                    let r = m.r - model.latency_at_concurrency_f64(m.n);
+                    let slope = if r >= 0.0 { quantile } else { 1.0 - quantile };
+                    (m, slope / r.abs().max(m.r * 1e-9))
+                })
+                .collect();
+            let prev = model;
+            model = Model::fit(ModelFitter { options, ..ModelFitter::weighted(&weighted) }, None);
+            if relative_eq!(model.sigma, prev.sigma)
+                && relative_eq!(model.kappa, prev.kappa)
+                && relative_eq!(model.lambda, prev.lambda)
+            {
+                break;
+            }
+        }
+        model
+    }
+
+    /// Build a model using the given fitting options.
+    #[must_use]
+    pub fn build_with_options(measurements: &[Measurement], options: &FitOptions) -> Model {
+        Model::fit(ModelFitter { options: *options, ..ModelFitter::new(measurements) }, None)
+    }
+
+    /// Build a model as with [Model::build], returning a [FitReport] with convergence diagnostics
+    /// alongside it.
+    #[must_use]
+    pub fn build_with_report(measurements: &[Measurement]) -> (Model, FitReport) {
         assert!(
             measurements.len() >= MIN_MEASUREMENTS,
             "must have at least {} measurements",
             MIN_MEASUREMENTS
         );
-        let fitter = ModelFitter(measurements.to_vec());
-        let mut params = fitter.init_params();
-        if let Err(err) = fitter.mpfit(&mut params, None, &Default::default()) {
-            panic!("lma error: {}", err)
+        match Model::try_fit_with_report(&ModelFitter::new(measurements), None) {
+            Ok(result) => result,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Build a model as with [Model::build], returning the standard errors of its parameters
+    /// alongside it.
+    ///
+    /// ```
+    /// use usl::{Model, Measurement};
+    ///
+    /// let measurements: Vec<Measurement> =
+    ///     [(1, 65.0), (18, 996.0), (36, 1652.0), (72, 1853.0), (108, 1829.0), (144, 1775.0)]
+    ///         .iter()
+    ///         .map(|&m| m.into())
+    ///         .collect();
+    /// let (model, errors) = Model::build_with_errors(&measurements);
+    /// println!("κ = {:e} ± {:e}", model.kappa, errors.kappa);
+    /// ```
+    #[must_use]
+    pub fn build_with_errors(measurements: &[Measurement]) -> (Model, StandardErrors) {
+        let (model, report) = Model::build_with_report(measurements);
+        (model, StandardErrors::from_report(&report))
+    }
+
+    /// Build a model using the given regression backend instead of the default [Lma] backend.
+    #[must_use]
+    pub fn build_with_fitter(measurements: &[Measurement], fitter: &impl Fitter) -> Model {
+        assert!(
+            measurements.len() >= MIN_MEASUREMENTS,
+            "must have at least {} measurements",
+            MIN_MEASUREMENTS
+        );
+        let objective = ModelFitter::new(measurements);
+        let mut params = objective.init_params();
+        if let Err(err) = fitter.minimize(&objective, &mut params) {
+            panic!("fit error: {}", err)
         }
         Model { sigma: params[0], kappa: params[1], lambda: params[2] }
     }
 
+    /// Build a new model from the given measurements, starting the fit from this model's
+    /// parameters.
+    ///
+    /// When refitting data which is similar to the data this model was fitted to, e.g. from
+    /// successive benchmark runs, this converges in fewer iterations and is less prone to
+    /// converging to different local minima from run to run.
+    #[must_use]
+    pub fn refit(&self, measurements: &[Measurement]) -> Model {
+        Model::fit(ModelFitter { start: Some(*self), ..ModelFitter::new(measurements) }, None)
+    }
+
+    fn fit(fitter: ModelFitter, constraints: Option<&[MPPar]>) -> Model {
+        assert!(
+            fitter.measurements.len() >= MIN_MEASUREMENTS,
+            "must have at least {} measurements",
+            MIN_MEASUREMENTS
+        );
+        match Model::try_fit(&fitter, constraints) {
+            Ok(model) => model,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    fn try_fit(fitter: &ModelFitter, constraints: Option<&[MPPar]>) -> Result<Model, FitError> {
+        Model::try_fit_with_report(fitter, constraints).map(|(model, _)| model)
+    }
+
+    fn try_fit_with_report(
+        fitter: &ModelFitter,
+        constraints: Option<&[MPPar]>,
+    ) -> Result<(Model, FitReport), FitError> {
+        let mut params = fitter.init_params();
+        for (p, _) in
+            params.iter_mut().zip(constraints.unwrap_or_default()).filter(|(_, c)| c.fixed)
+        {
+            *p = 0.0;
+        }
+        let initial = Model { sigma: params[0], kappa: params[1], lambda: params[2] };
+        let report = match LmaObjective(fitter).mpfit(&mut params, constraints, &Default::default())
+        {
+            Ok(status) => FitReport::from_status(initial, &status),
+            Err(err) => {
+                // Fall back to the slower but more forgiving Nelder-Mead algorithm.
+                return Model::try_fit_with_nelder_mead(
+                    fitter,
+                    initial,
+                    constraints,
+                    &NelderMead::default(),
+                )
+                .map_err(|_| FitError::from(err));
+            }
+        };
+        Ok((Model { sigma: params[0], kappa: params[1], lambda: params[2] }, report))
+    }
+
+    fn try_fit_with_nelder_mead(
+        fitter: &ModelFitter,
+        initial: Model,
+        constraints: Option<&[MPPar]>,
+        nelder_mead: &NelderMead,
+    ) -> Result<(Model, FitReport), FitError> {
+        let constraints = constraints.unwrap_or(&[]);
+        let fixed: Vec<bool> =
+            (0..3).map(|i| constraints.get(i).is_some_and(|c| c.fixed)).collect();
+        let bounds: Vec<(f64, f64)> = (0..3)
+            .map(|i| match constraints.get(i) {
+                Some(c) if c.limited_low => (c.limit_low, f64::INFINITY),
+                _ => (f64::NEG_INFINITY, f64::INFINITY),
+            })
+            .collect();
+        let mut params = vec![initial.sigma, initial.kappa, initial.lambda];
+        let (iterations, evaluations, converged) =
+            nelder_mead.minimize_constrained(fitter, &mut params, &fixed, &bounds)?;
+        let report = FitReport {
+            initial,
+            termination: if converged {
+                Termination::NelderMead
+            } else {
+                Termination::MaxIterations
+            },
+            iterations,
+            evaluations,
+            initial_chi_square: fitter.cost(&[initial.sigma, initial.kappa, initial.lambda]),
+            chi_square: fitter.cost(&params),
+            free_parameters: fixed.iter().filter(|&&f| !f).count(),
+            pegged_parameters: 0,
+            points: fitter.measurements.len(),
+            covariance: [[f64::NAN; 3]; 3],
+        };
+        Ok((Model { sigma: params[0], kappa: params[1], lambda: params[2] }, report))
+    }
+
     /// Calculate the expected throughput given a number of concurrent events, `X(N)`.
     ///
     /// See "Practical Scalability Analysis with the Universal Scalability Law, Equation 3".
     #[must_use]
     pub fn throughput_at_concurrency(&self, n: u32) -> f64 {
-        let n: f64 = n.into();
+        self.throughput_at_concurrency_f64(n.into())
+    }
+
+    /// Calculate the expected throughput given a possibly fractional average number of concurrent
+    /// events, `X(N)`.
+    #[must_use]
+    pub fn throughput_at_concurrency_f64(&self, n: f64) -> f64 {
         (self.lambda * n) / (1.0 + (self.sigma * (n - 1.0)) + (self.kappa * n * (n - 1.0)))
     }
 
@@ -175,18 +777,83 @@ impl Model {
     /// See "Practical Scalability Analysis with the Universal Scalability Law, Equation 6".
     #[must_use]
     pub fn latency_at_concurrency(&self, n: u32) -> f64 {
-        let n: f64 = n.into();
+        self.latency_at_concurrency_f64(n.into())
+    }
+
+    /// Calculate the expected mean latency given a possibly fractional average number of
+    /// concurrent events, `R(N)`.
+    #[must_use]
+    pub fn latency_at_concurrency_f64(&self, n: f64) -> f64 {
         (1.0 + (self.sigma * (n - 1.0)) + (self.kappa * n * (n - 1.0))) / self.lambda
     }
 
+    /// Calculate the expected mean latency given a number of concurrent events, `R(N)`, as a
+    /// [Duration].
+    ///
+    /// Returns `None` if the latency is negative or not finite, and so has no [Duration].
+    #[must_use]
+    pub fn latency_at_concurrency_duration(&self, n: u32) -> Option<Duration> {
+        Duration::try_from_secs_f64(self.latency_at_concurrency(n)).ok()
+    }
+
     /// Calculate the maximum expected number of concurrent events the system can handle, `N{max}`.
     ///
     /// See "Practical Scalability Analysis with the Universal Scalability Law, Equation 4".
+    ///
+    /// If the system has no finite peak, e.g. because it is [limitless](Model::is_limitless), the
+    /// result is meaningless. Use [Model::checked_max_concurrency] to distinguish that case.
     #[must_use]
     pub fn max_concurrency(&self) -> u32 {
         (((1.0 - self.sigma) / self.kappa).sqrt()).floor() as u32
     }
 
+    /// Calculate the maximum expected number of concurrent events the system can handle, `N{max}`,
+    /// returning `None` if the throughput curve has no finite peak which fits in a `u32`.
+    #[must_use]
+    pub fn checked_max_concurrency(&self) -> Option<u32> {
+        if self.is_limitless() {
+            return None;
+        }
+        let n = ((1.0 - self.sigma) / self.kappa).sqrt().floor();
+        (n.is_finite() && (0.0..=f64::from(u32::MAX)).contains(&n)).then_some(n as u32)
+    }
+
+    /// Find the range of concurrency levels over which throughput is retrograde, i.e. lower than
+    /// with one fewer concurrent event.
+    ///
+    /// Returns `None` if throughput never declines with added concurrency, i.e. if the model has
+    /// no finite peak.
+    #[must_use]
+    pub fn retrograde_region(&self) -> Option<RangeFrom<u32>> {
+        self.integral_peak().map(|n| (n + 1)..)
+    }
+
+    /// Calculate the throughput lost at a given number of concurrent events by operating beyond
+    /// the peak of the throughput curve, relative to the throughput at the peak.
+    ///
+    /// Returns zero if `n` is not in the [retrograde region](Model::retrograde_region).
+    #[must_use]
+    pub fn retrograde_loss_at_concurrency(&self, n: u32) -> f64 {
+        match self.integral_peak() {
+            Some(peak) if n > peak => {
+                self.throughput_at_concurrency(peak) - self.throughput_at_concurrency(n)
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// The integral concurrency level with the highest expected throughput, if any. This is either
+    /// [Model::max_concurrency] or one more than it.
+    fn integral_peak(&self) -> Option<u32> {
+        let n = self.checked_max_concurrency()?;
+        let next = n.checked_add(1)?;
+        Some(if self.throughput_at_concurrency(next) > self.throughput_at_concurrency(n) {
+            next
+        } else {
+            n
+        })
+    }
+
     /// Calculate the maximum expected throughput the system can handle, `X{max}`.
     #[must_use]
     pub fn max_throughput(&self) -> f64 {
@@ -201,6 +868,28 @@ impl Model {
         (self.sigma - 1.0) / (self.sigma * x - self.lambda)
     }
 
+    /// Calculate the expected mean latency given a throughput, `R(X)`, as a [Duration].
+    ///
+    /// Returns `None` if the latency is negative or not finite, e.g. for throughputs beyond the
+    /// model's asymptotic limit, and so has no [Duration].
+    #[must_use]
+    pub fn latency_at_throughput_duration(&self, x: f64) -> Option<Duration> {
+        Duration::try_from_secs_f64(self.latency_at_throughput(x)).ok()
+    }
+
+    /// Calculate the expected mean latency given a throughput, accounting for coherency.
+    ///
+    /// Solves `κX²R² + (σX - κX - λ)R + (1 - σ) = 0` for the lesser root, which lies on the
+    /// throughput curve before its peak. This is the same as [Model::latency_at_throughput] when κ
+    /// is zero. Throughputs beyond [Model::max_throughput] have no solution, so are given the
+    /// latency at the peak, which keeps the residuals finite while fitting.
+    fn latency_on_throughput_curve(&self, x: f64) -> f64 {
+        let b = self.lambda + self.kappa * x - self.sigma * x;
+        let c = 1.0 - self.sigma;
+        // Use the form of the quadratic formula which is stable as κ approaches zero.
+        2.0 * c / (b + (b * b - 4.0 * self.kappa * x * x * c).max(0.0).sqrt())
+    }
+
     /// Calculate the expected throughput given a mean latency, `X(R)`.
     ///
     /// See "Practical Scalability Analysis with the Universal Scalability Law, Equation 9".
@@ -236,6 +925,234 @@ impl Model {
         self.latency_at_throughput(x) * x
     }
 
+    /// Like [Model::latency_at_throughput], but returns an error if the throughput is outside the
+    /// domain in which the result is meaningful.
+    ///
+    /// Throughputs above [Model::max_throughput] can never be achieved, and throughputs at or
+    /// beyond `λ/σ` are at or past the singularity of the inverse function.
+    pub fn checked_latency_at_throughput(&self, x: f64) -> Result<f64, InverseError> {
+        if !(x.is_finite() && x >= 0.0) {
+            return Err(InverseError::InvalidThroughput(x));
+        }
+        let asymptote = self.lambda / self.sigma;
+        if x >= asymptote {
+            return Err(InverseError::Singularity { throughput: x, asymptote });
+        }
+        if self.checked_max_concurrency().is_some() {
+            let max_throughput = self.max_throughput();
+            if x > max_throughput {
+                return Err(InverseError::AbovePeak { throughput: x, max_throughput });
+            }
+        }
+        Ok(self.latency_at_throughput(x))
+    }
+
+    /// Like [Model::concurrency_at_throughput], but returns an error if the throughput is outside
+    /// the domain in which the result is meaningful. See [Model::checked_latency_at_throughput].
+    pub fn checked_concurrency_at_throughput(&self, x: f64) -> Result<f64, InverseError> {
+        Ok(self.checked_latency_at_throughput(x)? * x)
+    }
+
+    /// Calculate the fraction of ideal linear scaling retained at a given number of concurrent
+    /// events, `E(N) = X(N)/Nλ`.
+    ///
+    /// A linearly scalable system has an efficiency of 1 at any concurrency; contention and
+    /// coherency reduce it as concurrency increases.
+    #[must_use]
+    pub fn efficiency_at_concurrency(&self, n: u32) -> f64 {
+        self.throughput_at_concurrency(n) / (f64::from(n) * self.lambda)
+    }
+
+    /// Calculate the fraction of the system's modeled capacity in use at a given number of
+    /// concurrent events, `X(N)/X{max}`.
+    ///
+    /// Models with no finite peak have no [Model::max_throughput], so for them utilization is
+    /// relative to the asymptotic throughput, `λ/σ`. Linearly scalable models have no capacity
+    /// limit at all, so their utilization is zero.
+    #[must_use]
+    pub fn utilization_at_concurrency(&self, n: u32) -> f64 {
+        let capacity = match self.checked_max_concurrency() {
+            Some(_) => self.max_throughput(),
+            None => self.lambda / self.sigma,
+        };
+        self.throughput_at_concurrency(n) / capacity
+    }
+
+    /// Calculate the speedup at a given number of concurrent events relative to a single one,
+    /// `S(N) = X(N)/X(1)`.
+    ///
+    /// A linearly scalable system has a speedup of `N`.
+    #[must_use]
+    pub fn speedup_at_concurrency(&self, n: u32) -> f64 {
+        self.throughput_at_concurrency(n) / self.throughput_at_concurrency(1)
+    }
+
+    /// Calculate the fraction of ideal linear speedup which is lost over a range of concurrency
+    /// levels, `1 - ΣS(N)/ΣN`.
+    ///
+    /// This is 0 for a linearly scalable system and approaches 1 as the speedup over the range
+    /// flattens out or becomes retrograde.
+    ///
+    /// Panics if the range is empty or includes zero.
+    #[must_use]
+    pub fn deviation_from_linear(&self, range: RangeInclusive<u32>) -> f64 {
+        assert!(!range.is_empty() && *range.start() > 0, "range must be non-empty and positive");
+        let (speedup, ideal) = range
+            .fold((0.0, 0.0), |(s, i), n| (s + self.speedup_at_concurrency(n), i + f64::from(n)));
+        1.0 - speedup / ideal
+    }
+
+    /// Calculate the slope of the throughput curve at a given number of concurrent events,
+    /// `dX/dN = λ(1 - σ - κN²)/D(N)²`, where `D(N) = 1 + σ(N-1) + κN(N-1)`.
+    ///
+    /// The slope is zero at the peak of the curve and negative beyond it.
+    #[must_use]
+    pub fn throughput_gradient_at_concurrency(&self, n: u32) -> f64 {
+        let n: f64 = n.into();
+        let d = 1.0 + self.sigma * (n - 1.0) + self.kappa * n * (n - 1.0);
+        self.lambda * (1.0 - self.sigma - self.kappa * n * n) / (d * d)
+    }
+
+    /// Calculate the second derivative of the throughput curve at a given number of concurrent
+    /// events, `d²X/dN²`.
+    #[must_use]
+    pub fn throughput_second_derivative_at_concurrency(&self, n: u32) -> f64 {
+        let n: f64 = n.into();
+        let d = 1.0 + self.sigma * (n - 1.0) + self.kappa * n * (n - 1.0);
+        let dd = self.sigma + self.kappa * (2.0 * n - 1.0);
+        let u = 1.0 - self.sigma - self.kappa * n * n;
+        let du = -2.0 * self.kappa * n;
+        self.lambda * (du * d - 2.0 * u * dd) / (d * d * d)
+    }
+
+    /// Find the largest number of concurrent events at which the expected mean latency is within
+    /// the given objective, returning it along with the expected throughput at that concurrency.
+    ///
+    /// Returns `None` if even a single concurrent event is expected to exceed the objective.
+    #[must_use]
+    pub fn max_concurrency_within_latency(&self, slo: Duration) -> Option<(u32, f64)> {
+        let r = slo.as_secs_f64();
+
+        // Solve R(N) = r for the greater root of κN² + (σ - κ)N + (1 - σ - λr) = 0, using the form
+        // of the quadratic formula which is stable as κ approaches zero.
+        let b = self.sigma - self.kappa;
+        let c = self.lambda * r - 1.0 + self.sigma;
+        let root = 2.0 * c / (b + (b * b + 4.0 * self.kappa * c).max(0.0).sqrt());
+
+        // Correct for rounding errors in the root.
+        let mut n = root.floor() as u32;
+        if n < u32::MAX && self.latency_at_concurrency(n + 1) <= r {
+            n += 1;
+        } else if n > 0 && self.latency_at_concurrency(n) > r {
+            n -= 1;
+        }
+        (n > 0).then(|| (n, self.throughput_at_concurrency(n)))
+    }
+
+    /// Find the smallest number of concurrent events at which the expected throughput is at least
+    /// the given fraction of [Model::max_throughput].
+    ///
+    /// Panics if `fraction` is not between 0 and 1.
+    #[must_use]
+    pub fn concurrency_at_fraction_of_max(&self, fraction: f64) -> u32 {
+        assert!(fraction > 0.0 && fraction <= 1.0, "fraction must be between 0 and 1");
+        let x = fraction * self.max_throughput();
+        let root = self.ascending_concurrency(x).unwrap_or_else(|| self.max_concurrency().into());
+
+        // Correct for rounding errors in the root.
+        let n = (root.ceil() as u32).max(1);
+        if n > 1 && self.throughput_at_concurrency(n - 1) >= x {
+            n - 1
+        } else {
+            n
+        }
+    }
+
+    /// Calculate the number of concurrent events required to achieve a given throughput, on the
+    /// ascending branch of the throughput curve.
+    ///
+    /// Unlike [Model::concurrency_at_throughput], this accounts for coherency, and returns an error
+    /// if the throughput exceeds what the system can achieve.
+    pub fn concurrency_for_throughput(&self, x: f64) -> Result<f64, Infeasible> {
+        self.ascending_concurrency(x)
+            .ok_or(Infeasible { throughput: x, max_throughput: self.max_throughput() })
+    }
+
+    /// Solve `X(N) = x` for the lesser root of `κxN² - (λ + κx - σx)N + (1 - σ)x = 0`, using the
+    /// form of the quadratic formula which is stable as κ approaches zero. Returns `None` if there
+    /// is no non-negative root.
+    fn ascending_concurrency(&self, x: f64) -> Option<f64> {
+        let b = self.lambda + self.kappa * x - self.sigma * x;
+        let c = (1.0 - self.sigma) * x;
+        let discriminant = b * b - 4.0 * self.kappa * x * c;
+        if !(x >= 0.0 && b > 0.0 && discriminant >= 0.0) {
+            return None;
+        }
+        Some(2.0 * c / (b + discriminant.sqrt()))
+    }
+
+    /// Find the concurrency at which the marginal throughput of one more concurrent event,
+    /// `X(N+1) - X(N)`, first drops below the given fraction of λ.
+    ///
+    /// This is the point of diminishing returns, which is usually a far more sensible operating
+    /// point than [Model::max_concurrency]. Returns `None` if the marginal throughput never drops
+    /// below the threshold, e.g. for a linearly scalable system.
+    #[must_use]
+    pub fn knee(&self, threshold: f64) -> Option<u32> {
+        let below = |n: u32| {
+            self.throughput_at_concurrency(n + 1) - self.throughput_at_concurrency(n)
+                < threshold * self.lambda
+        };
+
+        // Find an upper bound by doubling, then bisect to find the first concurrency below the
+        // threshold, relying on the marginal throughput decreasing as concurrency increases.
+        let mut high = 1;
+        while !below(high) {
+            high = high.checked_mul(2).filter(|&n| n < u32::MAX)?;
+        }
+        let mut low = high / 2;
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if below(mid) {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+        Some(high)
+    }
+
+    /// Calculate a confidence interval for the expected throughput given a number of concurrent
+    /// events, using the given standard errors of the model's parameters.
+    ///
+    /// The interval is derived from the parameters' covariance via the delta method, and so widens
+    /// as `n` moves away from the measured concurrency levels. For intervals derived from bootstrap
+    /// samples, see [Bootstrap::interval].
+    #[must_use]
+    pub fn throughput_interval_at_concurrency(
+        &self,
+        n: u32,
+        confidence: f64,
+        errors: &StandardErrors,
+    ) -> (f64, f64) {
+        assert!((0.0..1.0).contains(&confidence), "confidence must be between 0 and 1");
+        let g = self.parameter_gradient(n.into());
+        let variance: f64 = (0..3)
+            .flat_map(|i| (0..3).map(move |j| (i, j)))
+            .map(|(i, j)| g[i] * errors.covariance[i][j] * g[j])
+            .sum();
+        let x = self.throughput_at_concurrency(n);
+        let margin = normal_quantile(0.5 + confidence / 2.0) * variance.sqrt();
+        (x - margin, x + margin)
+    }
+
+    /// The partial derivatives of `X(N)` with respect to σ, κ, and λ.
+    fn parameter_gradient(&self, n: f64) -> [f64; 3] {
+        let d = 1.0 + self.sigma * (n - 1.0) + self.kappa * n * (n - 1.0);
+        let x = self.lambda * n / d;
+        [-x * (n - 1.0) / d, -x * n * (n - 1.0) / d, n / d]
+    }
+
     /// Whether or not the system is constrained by contention effects.
     #[must_use]
     pub fn is_contention_constrained(&self) -> bool {
@@ -279,31 +1196,286 @@ from_iterator!(Duration, u32);
 from_iterator!(u32, Duration);
 from_iterator!(f64, Duration);
 from_iterator!(Duration, f64);
+from_iterator!(u16, f64);
+from_iterator!(f64, u16);
+from_iterator!(i32, f64);
+from_iterator!(f64, i32);
+from_iterator!(u64, f64);
+from_iterator!(f64, u64);
+from_iterator!(usize, f64);
+from_iterator!(f64, usize);
+from_iterator!(f32, f32);
 
-struct ModelFitter(Vec<Measurement>);
+fn median(sorted: &[f64]) -> f64 {
+    quantile(sorted, 0.5)
+}
 
-impl ModelFitter {
-    fn init_params(&self) -> Vec<f64> {
-        vec![0.1, 0.01, self.0.iter().map(|m| m.x / m.n).fold(f64::NEG_INFINITY, f64::max)]
-    }
+/// Estimate the standard deviation of the given residuals via their median absolute deviation,
+/// which is insensitive to a small number of outliers.
+fn robust_scale(residuals: &[f64]) -> f64 {
+    let mut abs: Vec<f64> = residuals.iter().map(|r| r.abs()).collect();
+    abs.sort_by(f64::total_cmp);
+    median(&abs) / 0.6745
 }
 
-impl MPFitter for ModelFitter {
-    fn eval(&self, params: &[f64], deviates: &mut [f64]) -> MPResult<()> {
-        let model = Model { sigma: params[0], kappa: params[1], lambda: params[2] };
-        for (d, m) in deviates.iter_mut().zip(self.0.iter()) {
-            *d = m.x - model.throughput_at_concurrency(m.n as u32);
-        }
-        Ok(())
+/// Calculate the `q`th quantile of the given sorted values, interpolating linearly between them.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let h = (sorted.len() - 1) as f64 * q;
+    let lo = h.floor() as usize;
+    match sorted.get(lo + 1) {
+        Some(hi) => sorted[lo] + (h - lo as f64) * (hi - sorted[lo]),
+        None => sorted[lo],
     }
+}
 
-    fn number_of_points(&self) -> usize {
-        self.0.len()
+/// Map each of the items to a fitted result, in parallel if the `rayon` feature is enabled.
+#[cfg(feature = "rayon")]
+fn map_fits<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R> {
+    use rayon::prelude::*;
+
+    items.par_iter().map(f).collect()
+}
+
+/// Map each of the items to a fitted result, in parallel if the `rayon` feature is enabled.
+#[cfg(not(feature = "rayon"))]
+fn map_fits<T, R>(items: &[T], f: impl Fn(&T) -> R) -> Vec<R> {
+    items.iter().map(f).collect()
+}
+
+/// Sample from the standard normal distribution via the Box-Muller transform.
+fn standard_normal(rng: &mut impl Rng) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+/// Calculate the central interval containing `confidence` of the values of `f` for the models.
+fn percentile_interval(models: &[Model], confidence: f64, f: impl Fn(&Model) -> f64) -> (f64, f64) {
+    assert!((0.0..=1.0).contains(&confidence), "confidence must be between 0 and 1");
+    let mut values: Vec<f64> = models.iter().map(f).collect();
+    values.sort_by(f64::total_cmp);
+    let alpha = (1.0 - confidence) / 2.0;
+    (quantile(&values, alpha), quantile(&values, 1.0 - alpha))
+}
+
+/// Calculate the quantile function of the standard normal distribution, via Acklam's algorithm.
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e+01,
+        2.209460984245205e+02,
+        -2.759285104469687e+02,
+        1.38357751867269e+02,
+        -3.066479806614716e+01,
+        2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01,
+        1.615858368580409e+02,
+        -1.556989798598866e+02,
+        6.680131188771972e+01,
+        -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03,
+        -3.223964580411365e-01,
+        -2.400758277161838e+00,
+        -2.549732539343734e+00,
+        4.374664141464968e+00,
+        2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03,
+        3.224671290700398e-01,
+        2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+/// Calculate the natural logarithm of the gamma function, via the Lanczos approximation.
+fn ln_gamma(x: f64) -> f64 {
+    const G: f64 = 7.0;
+    const C: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    let x = x - 1.0;
+    let t = x + G + 0.5;
+    let sum = C[1..].iter().enumerate().fold(C[0], |sum, (i, c)| sum + c / (x + i as f64 + 1.0));
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Calculate the regularized incomplete beta function, `I_x(a, b)`, via its continued fraction.
+fn incomplete_beta(x: f64, a: f64, b: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    } else if x >= 1.0 {
+        return 1.0;
+    }
+
+    // The continued fraction converges quickly only below this point, so use the symmetry
+    // relation above it.
+    if x > (a + 1.0) / (a + b + 2.0) {
+        return 1.0 - incomplete_beta(1.0 - x, b, a);
+    }
+
+    const TINY: f64 = 1e-300;
+    let ln_front = ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln();
+    let (mut c, mut d) = (1.0, 1.0 - (a + b) * x / (a + 1.0));
+    d = 1.0 / if d.abs() < TINY { TINY } else { d };
+    let mut f = d;
+    for m in 1..200 {
+        let m = f64::from(m);
+        for numerator in [
+            m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m)),
+            -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0)),
+        ] {
+            d = 1.0 + numerator * d;
+            d = 1.0 / if d.abs() < TINY { TINY } else { d };
+            c = 1.0 + numerator / c;
+            c = if c.abs() < TINY { TINY } else { c };
+            f *= c * d;
+        }
+        if (c * d - 1.0).abs() < 1e-15 {
+            break;
+        }
+    }
+    ln_front.exp() * f / a
+}
+
+/// Calculate the probability that an F-distributed variable with the given degrees of freedom
+/// exceeds `f`.
+fn f_survival(f: f64, d1: f64, d2: f64) -> f64 {
+    if f <= 0.0 {
+        return 1.0;
+    }
+    incomplete_beta(d2 / (d2 + d1 * f), d2 / 2.0, d1 / 2.0)
+}
+
+fn bounded_below(limit_low: f64) -> MPPar {
+    MPPar { limited_low: true, limit_low, ..Default::default() }
+}
+
+fn pinned() -> MPPar {
+    MPPar { fixed: true, ..Default::default() }
+}
+
+const SIGMA_GRID: [f64; 8] = [0.0, 0.001, 0.01, 0.05, 0.1, 0.2, 0.5, 0.9];
+
+const KAPPA_GRID: [f64; 7] = [0.0, 1e-6, 1e-5, 1e-4, 1e-3, 1e-2, 1e-1];
+
+struct ModelFitter {
+    measurements: Vec<Measurement>,
+    weights: Vec<f64>,
+    options: FitOptions,
+    start: Option<Model>,
+}
+
+impl ModelFitter {
+    fn new(measurements: &[Measurement]) -> ModelFitter {
+        ModelFitter {
+            measurements: measurements.to_vec(),
+            weights: vec![1.0; measurements.len()],
+            options: FitOptions::default(),
+            start: None,
+        }
+    }
+
+    fn weighted(measurements: &[(Measurement, f64)]) -> ModelFitter {
+        assert!(
+            measurements.iter().all(|&(_, w)| w >= 0.0 && w.is_finite()),
+            "weights must be non-negative and finite"
+        );
+        ModelFitter {
+            measurements: measurements.iter().map(|&(m, _)| m).collect(),
+            weights: measurements.iter().map(|&(_, w)| w).collect(),
+            options: FitOptions::default(),
+            start: None,
+        }
+    }
+
+    fn init_params(&self) -> Vec<f64> {
+        if let Some(m) = self.start {
+            return vec![m.sigma, m.kappa, m.lambda];
+        }
+
+        let lambda = self.measurements.iter().map(|m| m.x / m.n).fold(f64::NEG_INFINITY, f64::max);
+        if !self.options.grid_search {
+            return vec![0.1, 0.01, lambda];
+        }
+
+        let mut best = (f64::INFINITY, vec![0.1, 0.01, lambda]);
+        for &sigma in &SIGMA_GRID {
+            for &kappa in &KAPPA_GRID {
+                let params = vec![sigma, kappa, lambda];
+                let cost = self.cost(&params);
+                if cost < best.0 {
+                    best = (cost, params);
+                }
+            }
+        }
+        best.1
+    }
+
+    fn cost(&self, params: &[f64]) -> f64 {
+        let mut residuals = vec![0.0; self.measurements.len()];
+        self.residuals(params, &mut residuals);
+        residuals.iter().map(|r| r * r).sum()
+    }
+}
+
+impl Objective for ModelFitter {
+    fn residuals(&self, params: &[f64], deviates: &mut [f64]) {
+        let model = Model { sigma: params[0], kappa: params[1], lambda: params[2] };
+        for ((d, m), w) in
+            deviates.iter_mut().zip(self.measurements.iter()).zip(self.weights.iter())
+        {
+            let (observed, predicted) = match self.options.target {
+                FitTarget::Throughput => (m.x, model.throughput_at_concurrency_f64(m.n)),
+                FitTarget::Latency => (m.r, model.latency_at_concurrency_f64(m.n)),
+                FitTarget::LatencyAtThroughput => (m.r, model.latency_on_throughput_curve(m.x)),
+            };
+            let residual = match self.options.residual {
+                Residual::Absolute => observed - predicted,
+                Residual::Relative => (observed - predicted) / observed,
+                Residual::Log => observed.ln() - predicted.ln(),
+            };
+            *d = w.sqrt() * residual;
+        }
+    }
+
+    fn number_of_points(&self) -> usize {
+        self.measurements.len()
     }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
+    use std::cell::Cell;
+
     use approx::assert_relative_eq;
 
     use super::*;
@@ -326,6 +1498,84 @@ mod tests {
         assert_relative_eq!(m.x, 5.0);
     }
 
+    #[test]
+    fn try_measurement() {
+        let r = Duration::from_millis(600);
+        assert_relative_eq!(Measurement::try_concurrency_and_latency(3, r).unwrap().x, 5.0);
+        assert_relative_eq!(Measurement::try_concurrency_and_throughput(3, 5.0).unwrap().r, 0.6);
+        assert_relative_eq!(Measurement::try_throughput_and_latency(5.0, r).unwrap().n, 3.0);
+
+        assert_eq!(
+            Measurement::try_concurrency_and_latency(3, Duration::ZERO).unwrap_err(),
+            Problem::NonPositiveLatency
+        );
+        assert_eq!(
+            Measurement::try_concurrency_and_throughput(0, 5.0).unwrap_err(),
+            Problem::NonPositiveConcurrency
+        );
+        assert_eq!(
+            Measurement::try_concurrency_and_throughput(3, -5.0).unwrap_err(),
+            Problem::NonPositiveThroughput
+        );
+        assert_eq!(
+            Measurement::try_concurrency_and_throughput(3, f64::NAN).unwrap_err(),
+            Problem::NonFinite
+        );
+        assert_eq!(
+            Measurement::try_concurrency_and_throughput(3, 0.0).unwrap_err(),
+            Problem::NonPositiveThroughput
+        );
+        assert_eq!(
+            Measurement::try_concurrency_and_throughput(3, f64::INFINITY).unwrap_err(),
+            Problem::NonFinite
+        );
+        assert_eq!(
+            Measurement::try_throughput_and_latency(0.0, r).unwrap_err(),
+            Problem::NonPositiveThroughput
+        );
+        assert_eq!(
+            Measurement::try_throughput_and_latency(5.0, Duration::ZERO).unwrap_err(),
+            Problem::NonPositiveLatency
+        );
+    }
+
+    #[test]
+    fn measurement_from_tuples() {
+        for m in [
+            Measurement::from((3u16, 5.0)),
+            Measurement::from((5.0, 3u64)),
+            Measurement::from((3usize, 5.0)),
+            Measurement::from((3f32, 5f32)),
+        ] {
+            assert_relative_eq!(m.n, 3.0);
+            assert_relative_eq!(m.r, 0.6);
+            assert_relative_eq!(m.x, 5.0);
+        }
+
+        let measurements: Vec<(usize, f64)> =
+            MEASUREMENTS.iter().map(|&(n, x)| (n as usize, x)).collect();
+        let model: Model = measurements.iter().collect();
+        assert_relative_eq!(model.sigma, 0.02671591, max_relative = ACCURACY);
+        assert_relative_eq!(model.kappa, 7.690945e-4, max_relative = ACCURACY);
+        assert_relative_eq!(model.lambda, 995.6486, max_relative = ACCURACY);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        let json = serde_json::to_string(&model).unwrap();
+        let decoded: Model = serde_json::from_str(&json).unwrap();
+        assert_relative_eq!(decoded.sigma, model.sigma);
+        assert_relative_eq!(decoded.kappa, model.kappa);
+        assert_relative_eq!(decoded.lambda, model.lambda);
+
+        let m: Measurement = serde_json::from_str(r#"{"n":3.0,"x":5.0,"r":0.6}"#).unwrap();
+        assert_relative_eq!(m.n, 3.0);
+        assert_relative_eq!(m.x, 5.0);
+        assert_relative_eq!(m.r, 0.6);
+    }
+
     #[test]
     #[allow(clippy::cognitive_complexity)]
     fn build() {
@@ -383,9 +1633,563 @@ mod tests {
         );
     }
 
+    #[test]
+    fn try_build() {
+        let mut measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let model = Model::try_build(&measurements).expect("should build");
+        assert_relative_eq!(model.sigma, 0.02671591, max_relative = ACCURACY);
+
+        measurements[3].x = f64::NAN;
+        assert_eq!(
+            Model::try_build(&measurements).unwrap_err(),
+            BuildError::Invalid(ValidationError::InvalidMeasurements(vec![(
+                3,
+                Problem::NonFinite
+            )]))
+        );
+    }
+
+    #[test]
+    fn efficiency_at_concurrency() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        assert_relative_eq!(model.efficiency_at_concurrency(1), 1.0);
+        assert_relative_eq!(
+            model.efficiency_at_concurrency(20),
+            0.5555993,
+            max_relative = ACCURACY
+        );
+        assert!(model.efficiency_at_concurrency(35) < model.efficiency_at_concurrency(20));
+
+        let linear = Model { sigma: 0.0, kappa: 0.0, lambda: 100.0 };
+        assert_relative_eq!(linear.efficiency_at_concurrency(1000), 1.0);
+    }
+
+    #[test]
+    fn speedup_at_concurrency() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        assert_relative_eq!(model.speedup_at_concurrency(1), 1.0);
+        assert_relative_eq!(model.speedup_at_concurrency(20), 11.111986, max_relative = ACCURACY);
+        assert_relative_eq!(
+            model.speedup_at_concurrency(20),
+            20.0 * model.efficiency_at_concurrency(20),
+            max_relative = ACCURACY
+        );
+    }
+
+    #[test]
+    fn deviation_from_linear() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        assert_relative_eq!(model.deviation_from_linear(1..=1), 0.0);
+        let deviation = model.deviation_from_linear(1..=32);
+        assert!(deviation > model.deviation_from_linear(1..=16));
+        assert!(0.0 < deviation && deviation < 1.0);
+
+        let linear = Model { sigma: 0.0, kappa: 0.0, lambda: 100.0 };
+        assert_relative_eq!(linear.deviation_from_linear(1..=64), 0.0);
+    }
+
+    #[test]
+    fn throughput_derivatives_at_concurrency() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        let x = |n: f64| {
+            model.lambda * n / (1.0 + model.sigma * (n - 1.0) + model.kappa * n * (n - 1.0))
+        };
+        let h = 1e-3;
+        for n in [1, 10, 35, 100] {
+            let v = f64::from(n);
+            assert_relative_eq!(
+                model.throughput_gradient_at_concurrency(n),
+                (x(v + h) - x(v - h)) / (2.0 * h),
+                epsilon = 1e-4,
+                max_relative = 1e-6
+            );
+            assert_relative_eq!(
+                model.throughput_second_derivative_at_concurrency(n),
+                (x(v + h) - 2.0 * x(v) + x(v - h)) / (h * h),
+                max_relative = 1e-3
+            );
+        }
+        assert!(model.throughput_gradient_at_concurrency(35) > 0.0);
+        assert!(model.throughput_gradient_at_concurrency(36) < 0.0);
+    }
+
+    #[test]
+    fn concurrency_at_fraction_of_max() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        assert_eq!(model.concurrency_at_fraction_of_max(0.5), 8);
+        assert_eq!(model.concurrency_at_fraction_of_max(0.95), 25);
+        assert_eq!(model.concurrency_at_fraction_of_max(0.99), 30);
+        assert_eq!(model.concurrency_at_fraction_of_max(1.0), model.max_concurrency());
+    }
+
+    #[test]
+    fn checked_max_concurrency() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        assert_eq!(model.checked_max_concurrency(), Some(35));
+
+        let limitless = Model { sigma: 0.1, kappa: 0.0, lambda: 100.0 };
+        assert_eq!(limitless.checked_max_concurrency(), None);
+
+        let tiny = Model { sigma: 0.1, kappa: 1e-30, lambda: 100.0 };
+        assert_eq!(tiny.checked_max_concurrency(), None);
+
+        let superlinear = Model { sigma: 0.1, kappa: -1e-4, lambda: 100.0 };
+        assert_eq!(superlinear.checked_max_concurrency(), None);
+    }
+
+    #[test]
+    fn latency_durations() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        let r = model.latency_at_concurrency_duration(20).expect("should be a duration");
+        assert_relative_eq!(r.as_secs_f64(), model.latency_at_concurrency(20), epsilon = 1e-9);
+        assert_relative_eq!(model.concurrency_at_latency(r), 20.0, max_relative = 1e-6);
+
+        let r = model.latency_at_throughput_duration(7000.0).expect("should be a duration");
+        assert_relative_eq!(r.as_secs_f64(), model.latency_at_throughput(7000.0), epsilon = 1e-9);
+        assert_eq!(model.latency_at_throughput_duration(1e6), None);
+    }
+
+    #[test]
+    fn fractional_concurrency() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        assert_relative_eq!(
+            model.throughput_at_concurrency_f64(20.0),
+            model.throughput_at_concurrency(20)
+        );
+        assert_relative_eq!(
+            model.latency_at_concurrency_f64(20.0),
+            model.latency_at_concurrency(20)
+        );
+
+        let x = model.throughput_at_concurrency_f64(20.5);
+        assert!(model.throughput_at_concurrency(20) < x && x < model.throughput_at_concurrency(21));
+        let r = model.latency_at_concurrency_f64(20.5);
+        assert!(model.latency_at_concurrency(20) < r && r < model.latency_at_concurrency(21));
+    }
+
+    #[test]
+    fn retrograde_region() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        assert_eq!(model.retrograde_region(), Some(37..));
+        assert_relative_eq!(model.retrograde_loss_at_concurrency(36), 0.0);
+        assert_relative_eq!(
+            model.retrograde_loss_at_concurrency(100),
+            model.throughput_at_concurrency(36) - model.throughput_at_concurrency(100)
+        );
+        assert!(model.retrograde_loss_at_concurrency(100) > 0.0);
+
+        let limitless = Model { sigma: 0.1, kappa: 0.0, lambda: 100.0 };
+        assert_eq!(limitless.retrograde_region(), None);
+        assert_relative_eq!(limitless.retrograde_loss_at_concurrency(1000), 0.0);
+    }
+
+    #[test]
+    fn max_concurrency_within_latency() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        let within = |ms: f64| model.max_concurrency_within_latency(Duration::from_secs_f64(ms));
+        assert_eq!(within(0.0009), None);
+        assert_eq!(within(0.001005).map(|(n, _)| n), Some(1));
+        assert_eq!(within(0.002).map(|(n, _)| n), Some(23));
+        assert_eq!(within(0.01).map(|(n, _)| n), Some(92));
+
+        let (n, x) = within(0.002).unwrap();
+        assert_relative_eq!(x, model.throughput_at_concurrency(n));
+
+        let amdahl = Model { sigma: 0.1, kappa: 0.0, lambda: 100.0 };
+        let slo = Duration::from_micros(62_500);
+        assert_eq!(amdahl.max_concurrency_within_latency(slo).map(|(n, _)| n), Some(53));
+    }
+
+    #[test]
+    fn checked_inverse_functions() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        let x = model.throughput_at_concurrency(10);
+        assert_eq!(model.checked_latency_at_throughput(x), Ok(model.latency_at_throughput(x)));
+        assert_eq!(
+            model.checked_concurrency_at_throughput(x),
+            Ok(model.concurrency_at_throughput(x))
+        );
+
+        let above = model.max_throughput() * 1.1;
+        assert_eq!(
+            model.checked_latency_at_throughput(above),
+            Err(InverseError::AbovePeak {
+                throughput: above,
+                max_throughput: model.max_throughput()
+            })
+        );
+
+        let asymptote = model.lambda / model.sigma;
+        assert_eq!(
+            model.checked_concurrency_at_throughput(asymptote),
+            Err(InverseError::Singularity { throughput: asymptote, asymptote })
+        );
+        assert_eq!(
+            model.checked_latency_at_throughput(-1.0),
+            Err(InverseError::InvalidThroughput(-1.0))
+        );
+
+        let amdahl = Model { kappa: 0.0, ..model };
+        assert!(amdahl.checked_latency_at_throughput(asymptote * 0.99).is_ok());
+        assert!(matches!(
+            amdahl.checked_latency_at_throughput(asymptote * 2.0),
+            Err(InverseError::Singularity { .. })
+        ));
+    }
+
+    #[test]
+    fn concurrency_for_throughput() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        for n in [1, 10, 20, 35] {
+            let x = model.throughput_at_concurrency(n);
+            assert_relative_eq!(
+                model.concurrency_for_throughput(x).expect("should be feasible"),
+                f64::from(n),
+                max_relative = 1e-6
+            );
+        }
+        assert_eq!(
+            model.concurrency_for_throughput(20_000.0),
+            Err(Infeasible { throughput: 20_000.0, max_throughput: model.max_throughput() })
+        );
+
+        let amdahl = Model { sigma: 0.1, kappa: 0.0, lambda: 100.0 };
+        assert_relative_eq!(amdahl.concurrency_for_throughput(500.0).unwrap(), 9.0);
+        assert!(amdahl.concurrency_for_throughput(1_000.0).is_err());
+    }
+
+    #[test]
+    fn utilization_at_concurrency() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        assert_relative_eq!(model.utilization_at_concurrency(35), 1.0);
+        assert_relative_eq!(
+            model.utilization_at_concurrency(20),
+            model.throughput_at_concurrency(20) / model.max_throughput()
+        );
+
+        let amdahl = Model { sigma: 0.1, kappa: 0.0, lambda: 100.0 };
+        assert_relative_eq!(amdahl.utilization_at_concurrency(1), 0.1);
+        assert_relative_eq!(amdahl.utilization_at_concurrency(91), 0.91);
+
+        let linear = Model { sigma: 0.0, kappa: 0.0, lambda: 100.0 };
+        assert_relative_eq!(linear.utilization_at_concurrency(1000), 0.0);
+    }
+
+    #[test]
+    fn knee() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        assert_eq!(model.knee(0.5), Some(11));
+        assert_eq!(model.knee(0.1), Some(26));
+        assert_eq!(model.knee(0.0), Some(36));
+        assert_eq!(model.knee(2.0), Some(1));
+
+        let linear = Model { sigma: 0.0, kappa: 0.0, lambda: 100.0 };
+        assert_eq!(linear.knee(0.5), None);
+    }
+
+    #[test]
+    fn build_constrained() {
+        let measurements: Vec<Measurement> = SUPERLINEAR.iter().map(|&m| m.into()).collect();
+
+        let model = Model::build(&measurements);
+        assert!(model.sigma < 0.0);
+
+        let model = Model::build_constrained(&measurements);
+        assert!(model.sigma >= 0.0);
+        assert!(model.kappa >= 0.0);
+        assert!(model.lambda > 0.0);
+    }
+
+    #[test]
+    fn build_amdahl() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let model = Model::build_amdahl(&measurements);
+
+        assert_eq!(model.kappa, 0.0);
+        assert!(model.sigma > 0.0);
+        assert!(model.is_contention_constrained());
+    }
+
+    #[test]
+    fn build_contention_free() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let model = Model::build_contention_free(&measurements);
+
+        assert_eq!(model.sigma, 0.0);
+        assert!(model.kappa > 0.0);
+        assert!(model.is_coherency_constrained());
+    }
+
+    #[test]
+    fn build_weighted() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let expected = Model::build(&measurements[1..]);
+
+        let weighted: Vec<(Measurement, f64)> = measurements
+            .iter()
+            .enumerate()
+            .map(|(i, &m)| (m, if i == 0 { 0.0 } else { 1.0 }))
+            .collect();
+        let model = Model::build_weighted(&weighted);
+
+        assert_relative_eq!(model.sigma, expected.sigma, max_relative = ACCURACY);
+        assert_relative_eq!(model.kappa, expected.kappa, max_relative = ACCURACY);
+        assert_relative_eq!(model.lambda, expected.lambda, max_relative = ACCURACY);
+    }
+
+    #[test]
+    fn build_robust() {
+        let expected: Model = MEASUREMENTS.iter().collect();
+
+        let mut measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        measurements[20] = Measurement::concurrency_and_throughput(21, 3000.0);
+
+        let naive = Model::build(&measurements);
+        let robust = Model::build_robust(&measurements);
+
+        assert!((robust.kappa - expected.kappa).abs() < (naive.kappa - expected.kappa).abs());
+        assert!((robust.sigma - expected.sigma).abs() < (naive.sigma - expected.sigma).abs());
+        assert_relative_eq!(robust.kappa, expected.kappa, max_relative = 0.1);
+    }
+
+    #[test]
+    fn build_with_latency_target() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let sse = |model: &Model| -> f64 {
+            measurements.iter().map(|m| (m.r - model.latency_at_concurrency_f64(m.n)).powi(2)).sum()
+        };
+
+        let options = FitOptions { target: FitTarget::Latency, ..Default::default() };
+        let model = Model::build_with_options(&measurements, &options);
+
+        assert!(sse(&model) < sse(&Model::build(&measurements)));
+        assert_relative_eq!(model.lambda, 995.6486, max_relative = 0.05);
+    }
+
+    #[test]
+    fn build_quantile() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let below = |model: &Model| {
+            measurements
+                .iter()
+                .filter(|m| m.r <= model.latency_at_concurrency_f64(m.n) * (1.0 + 1e-6))
+                .count()
+        };
+
+        let median = Model::build_quantile(&measurements, 0.5);
+        let tail = Model::build_quantile(&measurements, 0.9);
+        assert!((12..=20).contains(&below(&median)), "{}", below(&median));
+        assert!(below(&tail) >= 27, "{}", below(&tail));
+        assert!(tail.latency_at_concurrency(16) > median.latency_at_concurrency(16));
+    }
+
+    #[test]
+    fn build_with_latency_at_throughput_target() {
+        let expected: Model = MEASUREMENTS.iter().collect();
+        // Stay clear of the peak, where latency is extremely sensitive to throughput.
+        let measurements: Vec<Measurement> = (1..=30)
+            .map(|n| {
+                Measurement::throughput_and_latency(
+                    expected.throughput_at_concurrency(n),
+                    Duration::from_secs_f64(expected.latency_at_concurrency(n)),
+                )
+            })
+            .collect();
+
+        let options = FitOptions {
+            target: FitTarget::LatencyAtThroughput,
+            grid_search: true,
+            ..Default::default()
+        };
+        let model = Model::build_with_options(&measurements, &options);
+        assert_relative_eq!(model.sigma, expected.sigma, max_relative = 0.001);
+        assert_relative_eq!(model.kappa, expected.kappa, max_relative = 0.001);
+        assert_relative_eq!(model.lambda, expected.lambda, max_relative = 0.001);
+    }
+
+    #[test]
+    fn build_with_relative_residuals() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let absolute = Model::build(&measurements);
+
+        for residual in [Residual::Relative, Residual::Log] {
+            let options = FitOptions { residual, ..Default::default() };
+            let model = Model::build_with_options(&measurements, &options);
+
+            // Relative residuals give the single-worker measurement more influence over λ.
+            assert!((model.lambda - 955.16).abs() < (absolute.lambda - 955.16).abs());
+        }
+    }
+
+    #[test]
+    fn build_with_fitter() {
+        struct Counting(Cell<usize>);
+
+        impl Fitter for Counting {
+            fn minimize(
+                &self,
+                objective: &dyn Objective,
+                params: &mut [f64],
+            ) -> Result<(), FitError> {
+                self.0.set(self.0.get() + 1);
+                Lma.minimize(objective, params)
+            }
+        }
+
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let fitter = Counting(Cell::new(0));
+        let model = Model::build_with_fitter(&measurements, &fitter);
+
+        assert_eq!(fitter.0.get(), 1);
+        assert_relative_eq!(model.sigma, 0.02671591, max_relative = ACCURACY);
+        assert_relative_eq!(model.kappa, 7.690945e-4, max_relative = ACCURACY);
+        assert_relative_eq!(model.lambda, 995.6486, max_relative = ACCURACY);
+    }
+
+    #[test]
+    #[should_panic(expected = "fit error: nope")]
+    fn build_with_failing_fitter() {
+        struct Failing;
+
+        impl Fitter for Failing {
+            fn minimize(&self, _: &dyn Objective, _: &mut [f64]) -> Result<(), FitError> {
+                Err(FitError::new("nope"))
+            }
+        }
+
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let _ = Model::build_with_fitter(&measurements, &Failing);
+    }
+
+    #[test]
+    fn build_with_nelder_mead() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let model = Model::build_with_fitter(&measurements, &NelderMead::default());
+
+        assert_relative_eq!(model.sigma, 0.02671591, max_relative = 0.001);
+        assert_relative_eq!(model.kappa, 7.690945e-4, max_relative = 0.001);
+        assert_relative_eq!(model.lambda, 995.6486, max_relative = 0.001);
+    }
+
+    #[test]
+    fn build_with_grid_search() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let options = FitOptions { grid_search: true, ..Default::default() };
+        let model = Model::build_with_options(&measurements, &options);
+
+        assert_relative_eq!(model.sigma, 0.02671591, max_relative = ACCURACY);
+        assert_relative_eq!(model.kappa, 7.690945e-4, max_relative = ACCURACY);
+        assert_relative_eq!(model.lambda, 995.6486, max_relative = ACCURACY);
+    }
+
+    #[test]
+    fn refit() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let (expected, cold) = Model::build_with_report(&measurements);
+        let warm = ModelFitter { start: Some(expected), ..ModelFitter::new(&measurements) };
+        let (_, warm) = Model::try_fit_with_report(&warm, None).expect("should fit");
+        assert!(warm.iterations < cold.iterations);
+
+        let model = expected.refit(&measurements);
+        assert_relative_eq!(model.sigma, expected.sigma, max_relative = ACCURACY);
+        assert_relative_eq!(model.kappa, expected.kappa, max_relative = ACCURACY);
+        assert_relative_eq!(model.lambda, expected.lambda, max_relative = ACCURACY);
+    }
+
+    #[test]
+    fn build_with_report() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let (model, report) = Model::build_with_report(&measurements);
+
+        assert_relative_eq!(model.sigma, 0.02671591, max_relative = ACCURACY);
+        assert!(report.termination.converged());
+        assert!(report.iterations > 0);
+        assert!(report.evaluations >= report.iterations);
+        assert!(report.chi_square < report.initial_chi_square);
+        assert_eq!(report.free_parameters, 3);
+        assert_eq!(report.pegged_parameters, 0);
+        assert_relative_eq!(report.initial.sigma, 0.1);
+        assert_relative_eq!(report.initial.kappa, 0.01);
+    }
+
+    #[test]
+    fn nelder_mead_report() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let fitter = ModelFitter::new(&measurements);
+        let initial = Model { sigma: 0.1, kappa: 0.01, lambda: 1000.0 };
+
+        let (_, report) =
+            Model::try_fit_with_nelder_mead(&fitter, initial, None, &NelderMead::default())
+                .expect("should fit");
+        assert_eq!(report.termination, Termination::NelderMead);
+        assert!(report.termination.converged());
+
+        let stopped = NelderMead { max_iter: 5, ..Default::default() };
+        let (_, report) =
+            Model::try_fit_with_nelder_mead(&fitter, initial, None, &stopped).expect("should fit");
+        assert_eq!(report.termination, Termination::MaxIterations);
+        assert!(!report.termination.converged());
+        assert_eq!(report.iterations, 5);
+    }
+
+    #[test]
+    fn build_with_errors() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let (model, errors) = Model::build_with_errors(&measurements);
+
+        assert_relative_eq!(model.kappa, 7.690945e-4, max_relative = ACCURACY);
+        assert!(errors.sigma > 0.0 && errors.sigma < model.sigma);
+        assert!(errors.kappa > 0.0 && errors.kappa < model.kappa);
+        assert!(errors.lambda > 0.0 && errors.lambda < model.lambda);
+        assert_relative_eq!(errors.covariance[0][1], errors.covariance[1][0]);
+        assert_relative_eq!(errors.covariance[1][1].sqrt(), errors.kappa);
+    }
+
+    #[test]
+    fn throughput_interval_at_concurrency() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let (model, errors) = Model::build_with_errors(&measurements);
+
+        let (low, high) = model.throughput_interval_at_concurrency(20, 0.95, &errors);
+        let x = model.throughput_at_concurrency(20);
+        assert!(low < x && x < high);
+        assert_relative_eq!(x - low, high - x, max_relative = ACCURACY);
+
+        let (far_low, far_high) = model.throughput_interval_at_concurrency(500, 0.95, &errors);
+        assert!((far_high - far_low) / model.throughput_at_concurrency(500) > (high - low) / x);
+    }
+
+    #[test]
+    fn normal_quantiles() {
+        assert_relative_eq!(normal_quantile(0.5), 0.0);
+        assert_relative_eq!(normal_quantile(0.975), 1.959963984540054, max_relative = 1e-8);
+        assert_relative_eq!(normal_quantile(0.01), -2.3263478740408408, max_relative = 1e-8);
+    }
+
+    #[test]
+    fn f_distribution() {
+        assert_relative_eq!(ln_gamma(1.0), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(ln_gamma(10.0), 362_880f64.ln(), max_relative = 1e-12);
+        assert_relative_eq!(incomplete_beta(0.5, 2.0, 2.0), 0.5, max_relative = 1e-10);
+        assert_relative_eq!(f_survival(3.098_391, 3.0, 20.0), 0.05, max_relative = 1e-5);
+        assert_relative_eq!(f_survival(4.964_603, 1.0, 10.0), 0.05, max_relative = 1e-5);
+        assert_relative_eq!(f_survival(0.0, 3.0, 20.0), 1.0);
+    }
+
     const ACCURACY: f64 = 0.00001;
 
-    const MEASUREMENTS: [(u32, f64); 32] = [
+    const SUPERLINEAR: [(u32, f64); 8] = [
+        (1, 100.0),
+        (2, 205.0),
+        (3, 312.0),
+        (4, 420.0),
+        (5, 525.0),
+        (6, 628.0),
+        (7, 700.0),
+        (8, 740.0),
+    ];
+
+    pub(crate) const MEASUREMENTS: [(u32, f64); 32] = [
         (1, 955.16),
         (2, 1878.91),
         (3, 2688.01),
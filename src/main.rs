@@ -1,3 +1,5 @@
+use std::fs::File;
+use std::io::BufReader;
 use std::path::PathBuf;
 
 use anyhow::Result;
@@ -7,13 +9,13 @@ use plotlib::repr::Plot;
 use plotlib::style::{PointMarker, PointStyle};
 use plotlib::view::ContinuousView;
 
-use usl::{Measurement, Model};
+use usl::{CsvOptions, Dataset, Model};
 
 /// Build and evaluate Universal Scalability Law models.
 #[derive(Debug, Parser)]
 #[clap(author, version, about)]
 struct Opts {
-    /// Path to input CSV file.
+    /// Path to input CSV or JSON file.
     #[clap(action, value_hint = ValueHint::FilePath)]
     input: PathBuf,
 
@@ -29,27 +31,30 @@ struct Opts {
 fn main() -> Result<()> {
     let opts: Opts = Opts::parse();
 
-    let mut measurments = Vec::new();
-    let mut input = csv::Reader::from_path(&opts.input)?;
-    for record in input.records() {
-        let record = record?;
-        let m = Measurement::concurrency_and_throughput(record[0].parse()?, record[1].parse()?);
-        measurments.push(m);
-    }
+    let measurments = if opts.input.extension().is_some_and(|ext| ext == "json") {
+        Dataset::from_json_reader(BufReader::new(File::open(&opts.input)?))?
+    } else {
+        Dataset::from_csv_path(&opts.input, &CsvOptions::default())?
+    };
 
-    let model = Model::build(&measurments);
-    println!("USL parameters: σ={:.6}, κ={:.6}, λ={:.6}", model.sigma, model.lambda, model.kappa);
-    println!(
-        "\tmax throughput: {:.6}, max concurrency: {:.6}",
-        model.max_throughput(),
-        model.max_concurrency()
-    );
-    if model.is_contention_constrained() {
-        println!("\tcontention constrained");
-    } else if model.is_coherency_constrained() {
-        println!("\tcoherency constrained");
-    } else if model.is_limitless() {
-        println!("\tlinearly scalable");
+    let model = Model::try_build(&measurments)?;
+    let summary = model.summary(&measurments);
+    let Model { sigma, kappa, lambda } = summary.model;
+    println!("USL parameters: σ={:.6}, κ={:.6}, λ={:.6}", sigma, kappa, lambda);
+    match summary.max_concurrency {
+        Some(n) => {
+            println!("\tmax throughput: {:.6}, max concurrency: {}", summary.max_throughput, n)
+        }
+        None => {
+            println!("\tmax throughput: {:.6}, no finite max concurrency", summary.max_throughput)
+        }
+    }
+    if let Some(knee) = summary.knee {
+        println!("\tknee: {}", knee);
+    }
+    println!("\tR²: {:.6}", summary.quality.r_squared);
+    if let Some(classification) = summary.classification {
+        println!("\t{}", classification);
     }
 
     if opts.plot {
@@ -58,9 +63,10 @@ fn main() -> Result<()> {
         let observed =
             Plot::new(observed).point_style(PointStyle::new().marker(PointMarker::Square));
 
-        let predicted = (0..(max_n as usize))
-            .step_by(max_n as usize / 10)
-            .map(|n| (n as f64, model.throughput_at_concurrency(n as u32)))
+        let predicted = model
+            .sweep(0..=(max_n as u32), (max_n as u32 / 10).max(1))
+            .iter()
+            .map(|p| (p.concurrency.into(), p.throughput))
             .collect();
         let predicted =
             Plot::new(predicted).point_style(PointStyle::new().marker(PointMarker::Circle));
@@ -0,0 +1,76 @@
+use std::ops::RangeInclusive;
+
+use crate::Model;
+
+/// A summary of the marginal throughput over a range of concurrency levels, via
+/// [Model::marginal_throughput_over].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarginalSummary {
+    /// The total change in throughput across the range, `X(end) - X(start)`.
+    pub total: f64,
+    /// The mean marginal throughput per additional concurrent event.
+    pub mean: f64,
+    /// The smallest marginal throughput in the range, which is negative if the range extends
+    /// beyond the peak.
+    pub min: f64,
+    /// The largest marginal throughput in the range.
+    pub max: f64,
+}
+
+impl Model {
+    /// Calculate the expected change in throughput from adding one more concurrent event (e.g. one
+    /// more worker or pod) to `n`, `X(N+1) - X(N)`.
+    ///
+    /// Beyond the peak, this is negative.
+    #[must_use]
+    pub fn marginal_throughput(&self, n: u32) -> f64 {
+        let n = f64::from(n);
+        self.throughput_at_concurrency_f64(n + 1.0) - self.throughput_at_concurrency_f64(n)
+    }
+
+    /// Summarize the marginal throughput of each additional concurrent event from the start of the
+    /// given range to its end.
+    ///
+    /// Panics if the range contains fewer than two concurrency levels.
+    #[must_use]
+    pub fn marginal_throughput_over(&self, range: RangeInclusive<u32>) -> MarginalSummary {
+        let (start, end) = range.into_inner();
+        assert!(start < end, "range must contain at least two concurrency levels");
+        let (min, max) = (start..end)
+            .map(|n| self.marginal_throughput(n))
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+        let total = self.throughput_at_concurrency(end) - self.throughput_at_concurrency(start);
+        MarginalSummary { total, mean: total / f64::from(end - start), min, max }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn marginal_throughput() {
+        let model: Model = MEASUREMENTS.iter().collect();
+
+        assert_relative_eq!(
+            model.marginal_throughput(32),
+            model.throughput_at_concurrency(33) - model.throughput_at_concurrency(32)
+        );
+        assert!(model.marginal_throughput(1) > model.marginal_throughput(10));
+        assert!(model.marginal_throughput(35) > 0.0);
+        assert!(model.marginal_throughput(36) < 0.0);
+
+        let summary = model.marginal_throughput_over(1..=50);
+        assert_relative_eq!(
+            summary.total,
+            model.throughput_at_concurrency(50) - model.throughput_at_concurrency(1)
+        );
+        assert_relative_eq!(summary.mean, summary.total / 49.0);
+        assert_relative_eq!(summary.max, model.marginal_throughput(1));
+        assert_relative_eq!(summary.min, model.marginal_throughput(49));
+    }
+}
@@ -0,0 +1,189 @@
+use crate::fitter::minimize_with_fallback;
+use crate::{Measurement, Model, Objective, MIN_MEASUREMENTS};
+
+/// An observation of a system's throughput at a particular concurrency and value of a second
+/// covariate, e.g. payload size.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Observation {
+    /// The average number of concurrent events.
+    pub n: f64,
+    /// The value of the covariate.
+    pub covariate: f64,
+    /// The long-term arrival rate of events, in events/sec.
+    pub x: f64,
+}
+
+impl From<(u32, f64, f64)> for Observation {
+    fn from((n, covariate, x): (u32, f64, f64)) -> Self {
+        Observation { n: n.into(), covariate, x }
+    }
+}
+
+/// Which of a [MultiFactorModel]'s σ and κ vary with the covariate, in addition to λ.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Varying {
+    /// Whether σ varies with the covariate.
+    pub sigma: bool,
+    /// Whether κ varies with the covariate.
+    pub kappa: bool,
+}
+
+/// An experimental Universal Scalability Law model of a system benchmarked across both
+/// concurrency and a second covariate, e.g. payload size.
+///
+/// Each parameter is a linear function of the covariate, `p(c) = p₀ + p₁c`, given as
+/// `(p₀, p₁)`. λ always varies with the covariate; σ and κ vary only if requested, and otherwise
+/// have a slope of zero. Fitting all the observations jointly uses information which is lost by
+/// fitting a separate [Model] for each value of the covariate.
+///
+/// ```
+/// use usl::{MultiFactorModel, Observation, Varying};
+///
+/// let observations: Vec<Observation> = [
+///     (1, 1.0, 995.0), (8, 1.0, 6900.0), (16, 1.0, 10600.0), (32, 1.0, 12300.0),
+///     (1, 4.0, 810.0), (8, 4.0, 5600.0), (16, 4.0, 8500.0), (32, 4.0, 9900.0),
+/// ]
+/// .iter()
+/// .map(|&o| o.into())
+/// .collect();
+/// let model = MultiFactorModel::build(&observations, Varying::default());
+/// println!("X(16, 2KiB) = {}", model.throughput_at(16.0, 2.0));
+/// ```
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiFactorModel {
+    /// The intercept and slope of the model's coefficient of contention, σ.
+    pub sigma: (f64, f64),
+    /// The intercept and slope of the model's coefficient of crosstalk/coherency, κ.
+    pub kappa: (f64, f64),
+    /// The intercept and slope of the model's coefficient of performance, λ.
+    pub lambda: (f64, f64),
+}
+
+impl MultiFactorModel {
+    /// Build a model whose parameters are generated from the given observations, with σ and κ
+    /// varying with the covariate as given.
+    ///
+    /// The fit starts from the parameters of a [Model] fitted to the same observations ignoring
+    /// the covariate, with every slope at zero.
+    #[must_use]
+    pub fn build(observations: &[Observation], varying: Varying) -> MultiFactorModel {
+        let fitter = MultiFactorFitter { observations, varying };
+        assert!(
+            observations.len() >= MIN_MEASUREMENTS.max(fitter.parameters()),
+            "must have at least {} observations",
+            MIN_MEASUREMENTS.max(fitter.parameters())
+        );
+
+        let measurements: Vec<Measurement> =
+            observations.iter().map(|o| Measurement { n: o.n, x: o.x, r: o.n / o.x }).collect();
+        let start = Model::build(&measurements);
+        let mut params = vec![0.0; fitter.parameters()];
+        params[..3].copy_from_slice(&[start.sigma, start.kappa, start.lambda]);
+        if let Err(err) = minimize_with_fallback(&fitter, &mut params) {
+            panic!("{}", err)
+        }
+        fitter.model(&params)
+    }
+
+    /// Calculate the one-dimensional model of the system at the given value of the covariate.
+    #[must_use]
+    pub fn at(&self, covariate: f64) -> Model {
+        let linear = |(p0, p1): (f64, f64)| p0 + p1 * covariate;
+        Model { sigma: linear(self.sigma), kappa: linear(self.kappa), lambda: linear(self.lambda) }
+    }
+
+    /// Calculate the expected throughput given a possibly fractional average number of concurrent
+    /// events and a value of the covariate, `X(N, c)`.
+    #[must_use]
+    pub fn throughput_at(&self, n: f64, covariate: f64) -> f64 {
+        self.at(covariate).throughput_at_concurrency_f64(n)
+    }
+
+    /// Calculate the expected mean latency given a possibly fractional average number of
+    /// concurrent events and a value of the covariate, `R(N, c)`.
+    #[must_use]
+    pub fn latency_at(&self, n: f64, covariate: f64) -> f64 {
+        self.at(covariate).latency_at_concurrency_f64(n)
+    }
+}
+
+struct MultiFactorFitter<'a> {
+    observations: &'a [Observation],
+    varying: Varying,
+}
+
+impl MultiFactorFitter<'_> {
+    /// The parameters are σ₀, κ₀, λ₀, and λ₁, followed by σ₁ and κ₁ if they vary.
+    fn parameters(&self) -> usize {
+        4 + usize::from(self.varying.sigma) + usize::from(self.varying.kappa)
+    }
+
+    fn model(&self, params: &[f64]) -> MultiFactorModel {
+        let mut slopes = params[4..].iter();
+        let sigma = if self.varying.sigma { *slopes.next().expect("σ₁") } else { 0.0 };
+        let kappa = if self.varying.kappa { *slopes.next().expect("κ₁") } else { 0.0 };
+        MultiFactorModel {
+            sigma: (params[0], sigma),
+            kappa: (params[1], kappa),
+            lambda: (params[2], params[3]),
+        }
+    }
+}
+
+impl Objective for MultiFactorFitter<'_> {
+    fn number_of_points(&self) -> usize {
+        self.observations.len()
+    }
+
+    fn residuals(&self, params: &[f64], residuals: &mut [f64]) {
+        let model = self.model(params);
+        for (r, o) in residuals.iter_mut().zip(self.observations) {
+            *r = o.x - model.throughput_at(o.n, o.covariate);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn build() {
+        let expected = MultiFactorModel {
+            sigma: (0.02, 0.0001),
+            kappa: (0.0001, 0.0),
+            lambda: (1000.0, -2.0),
+        };
+        let observations: Vec<Observation> = [10.0, 100.0, 200.0]
+            .iter()
+            .flat_map(|&c| {
+                (1..=40).map(move |n| Observation {
+                    n: n.into(),
+                    covariate: c,
+                    x: expected.throughput_at(n.into(), c),
+                })
+            })
+            .collect();
+
+        let model = MultiFactorModel::build(&observations, Varying { sigma: true, kappa: false });
+        assert_relative_eq!(model.sigma.0, expected.sigma.0, max_relative = 0.001);
+        assert_relative_eq!(model.sigma.1, expected.sigma.1, max_relative = 0.001);
+        assert_relative_eq!(model.kappa.0, expected.kappa.0, max_relative = 0.001);
+        assert_eq!(model.kappa.1, 0.0);
+        assert_relative_eq!(model.lambda.0, expected.lambda.0, max_relative = 0.001);
+        assert_relative_eq!(model.lambda.1, expected.lambda.1, max_relative = 0.001);
+
+        let at = model.at(150.0);
+        assert_relative_eq!(at.lambda, 700.0, max_relative = 0.001);
+        assert_relative_eq!(
+            model.latency_at(20.0, 150.0),
+            at.latency_at_concurrency(20),
+            max_relative = 1e-9
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use rand::Rng;
+
+use crate::{map_fits, Measurement, Model, ModelFitter, MIN_MEASUREMENTS};
+
+/// The result of building a model via [Model::build_multistart].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Multistart {
+    /// The fitted model with the lowest sum of squared residuals.
+    pub model: Model,
+    /// The minimum and maximum fitted values of σ across all starts.
+    pub sigma: (f64, f64),
+    /// The minimum and maximum fitted values of κ across all starts.
+    pub kappa: (f64, f64),
+    /// The minimum and maximum fitted values of λ across all starts.
+    pub lambda: (f64, f64),
+}
+
+impl Model {
+    /// Build a model by fitting from several randomized starting points.
+    ///
+    /// The first start uses the same initial parameters as [Model::build]; the remaining
+    /// `n_starts - 1` use random values of σ, κ, and λ. The fitted model with the lowest sum of
+    /// squared residuals is returned, along with the range of each fitted parameter. A wide range
+    /// indicates the objective is badly conditioned for the given measurements.
+    #[must_use]
+    pub fn build_multistart(measurements: &[Measurement], n_starts: usize) -> Multistart {
+        Model::build_multistart_with_rng(measurements, n_starts, &mut rand::thread_rng())
+    }
+
+    /// Like [Model::build_multistart], but using the given random number generator.
+    ///
+    /// Passing a seeded generator, e.g. `StdRng::seed_from_u64`, makes the results reproducible.
+    #[must_use]
+    pub fn build_multistart_with_rng(
+        measurements: &[Measurement],
+        n_starts: usize,
+        rng: &mut impl Rng,
+    ) -> Multistart {
+        assert!(
+            measurements.len() >= MIN_MEASUREMENTS,
+            "must have at least {} measurements",
+            MIN_MEASUREMENTS
+        );
+        assert!(n_starts > 0, "must have at least one start");
+
+        // Draw every starting point up front, so the result is the same whether or not the fits
+        // are run in parallel.
+        let lambda = ModelFitter::new(measurements).init_params()[2];
+        let starts: Vec<Option<Model>> = (0..n_starts)
+            .map(|i| {
+                (i > 0).then(|| Model {
+                    sigma: rng.gen_range(0.0..1.0),
+                    kappa: 10f64.powf(rng.gen_range(-6.0..-1.0)),
+                    lambda: lambda * rng.gen_range(0.5..2.0),
+                })
+            })
+            .collect();
+
+        let models: Vec<(f64, Model)> = map_fits(&starts, |&start| {
+            let fitter = ModelFitter { start, ..ModelFitter::new(measurements) };
+            let model = Model::try_fit(&fitter, None).ok()?;
+            let cost = fitter.cost(&[model.sigma, model.kappa, model.lambda]);
+            cost.is_finite().then_some((cost, model))
+        })
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let (_, model) =
+            *models.iter().min_by(|a, b| a.0.total_cmp(&b.0)).expect("no start converged");
+        let range = |f: fn(&Model) -> f64| {
+            models
+                .iter()
+                .map(|(_, m)| f(m))
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)))
+        };
+        Multistart {
+            model,
+            sigma: range(|m| m.sigma),
+            kappa: range(|m| m.kappa),
+            lambda: range(|m| m.lambda),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn build_multistart() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let mut rng = StdRng::seed_from_u64(0xDEADBEEF);
+        let multistart = Model::build_multistart_with_rng(&measurements, 10, &mut rng);
+
+        assert_relative_eq!(multistart.model.sigma, 0.02671591, max_relative = 0.00001);
+        assert_relative_eq!(multistart.model.kappa, 7.690945e-4, max_relative = 0.00001);
+        assert_relative_eq!(multistart.model.lambda, 995.6486, max_relative = 0.00001);
+        assert!(multistart.sigma.0 <= multistart.model.sigma);
+        assert!(multistart.sigma.1 >= multistart.model.sigma);
+        assert!(multistart.kappa.0 <= multistart.kappa.1);
+        assert!(multistart.lambda.0 <= multistart.lambda.1);
+    }
+}
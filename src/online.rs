@@ -0,0 +1,134 @@
+use std::collections::VecDeque;
+
+use crate::{Measurement, Model, ModelFitter, MIN_MEASUREMENTS};
+
+/// A model of a live system which is refitted as new measurements arrive.
+///
+/// Measurements are added via [OnlineModel::add], and the model is refitted lazily the next time
+/// it's requested via [OnlineModel::model]. Each refit starts from the previous model's parameters,
+/// so refitting after a few new measurements is cheap.
+///
+/// ```
+/// use usl::{Measurement, OnlineModel};
+///
+/// let mut online = OnlineModel::with_capacity(100);
+/// for &(n, x) in &[(1, 65.0), (18, 996.0), (36, 1652.0), (72, 1853.0), (108, 1829.0)] {
+///     online.add(Measurement::concurrency_and_throughput(n, x));
+/// }
+/// assert!(online.model().is_none());
+///
+/// online.add(Measurement::concurrency_and_throughput(144, 1775.0));
+/// println!("{:?}", online.model());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OnlineModel {
+    measurements: VecDeque<Measurement>,
+    capacity: Option<usize>,
+    model: Option<Model>,
+    stale: bool,
+}
+
+impl OnlineModel {
+    /// Create an online model which retains every measurement added to it.
+    #[must_use]
+    pub fn new() -> OnlineModel {
+        OnlineModel::default()
+    }
+
+    /// Create an online model which retains only the most recent `capacity` measurements.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> OnlineModel {
+        assert!(capacity >= MIN_MEASUREMENTS, "capacity must be at least {}", MIN_MEASUREMENTS);
+        OnlineModel {
+            measurements: VecDeque::with_capacity(capacity),
+            capacity: Some(capacity),
+            ..OnlineModel::default()
+        }
+    }
+
+    /// Add a measurement, evicting the oldest measurement if the model is at capacity.
+    pub fn add(&mut self, measurement: Measurement) {
+        if self.capacity == Some(self.measurements.len()) {
+            self.measurements.pop_front();
+        }
+        self.measurements.push_back(measurement);
+        self.stale = true;
+    }
+
+    /// The number of retained measurements.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.measurements.len()
+    }
+
+    /// Whether or not there are no retained measurements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.measurements.is_empty()
+    }
+
+    /// The retained measurements, oldest first.
+    pub fn measurements(&self) -> impl Iterator<Item = &Measurement> {
+        self.measurements.iter()
+    }
+
+    /// Return the model fitted to the retained measurements, refitting it if any measurements have
+    /// been added since it was last fitted.
+    ///
+    /// Returns `None` if fewer than [MIN_MEASUREMENTS] measurements have been added. If a refit
+    /// fails, the previous model is returned.
+    pub fn model(&mut self) -> Option<Model> {
+        if self.stale && self.measurements.len() >= MIN_MEASUREMENTS {
+            let measurements: Vec<Measurement> = self.measurements.iter().copied().collect();
+            let fitter = ModelFitter { start: self.model, ..ModelFitter::new(&measurements) };
+            if let Ok(model) = Model::try_fit(&fitter, None) {
+                self.model = Some(model);
+            }
+            self.stale = false;
+        }
+        self.model
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn online_model() {
+        let mut online = OnlineModel::new();
+        for &m in &MEASUREMENTS[..5] {
+            online.add(m.into());
+        }
+        assert_eq!(online.len(), 5);
+        assert!(online.model().is_none());
+
+        for &m in &MEASUREMENTS[5..] {
+            online.add(m.into());
+            assert!(online.model().is_some());
+        }
+
+        let model = online.model().unwrap();
+        assert_relative_eq!(model.sigma, 0.02671591, max_relative = 0.00001);
+        assert_relative_eq!(model.kappa, 7.690945e-4, max_relative = 0.00001);
+        assert_relative_eq!(model.lambda, 995.6486, max_relative = 0.00001);
+    }
+
+    #[test]
+    fn online_model_with_capacity() {
+        let mut online = OnlineModel::with_capacity(10);
+        for &m in &MEASUREMENTS {
+            online.add(m.into());
+        }
+        assert_eq!(online.len(), 10);
+        assert_eq!(online.measurements().next().unwrap().n, 23.0);
+
+        let expected = Model::build(
+            &MEASUREMENTS[22..].iter().map(|&m| m.into()).collect::<Vec<Measurement>>(),
+        );
+        assert_relative_eq!(online.model().unwrap().kappa, expected.kappa, max_relative = 0.0001);
+    }
+}
@@ -0,0 +1,63 @@
+//! Functions for detecting outlying measurements.
+
+use crate::{robust_scale, Measurement, Model};
+
+/// The measurements flagged as outliers by [detect].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Outliers {
+    /// The standardized residual of each measurement, in the same order as the measurements.
+    pub residuals: Vec<f64>,
+    /// The indexes of the measurements whose standardized residuals exceed the threshold.
+    pub indexes: Vec<usize>,
+}
+
+impl Outliers {
+    /// Return the given measurements without those flagged as outliers.
+    #[must_use]
+    pub fn clean(&self, measurements: &[Measurement]) -> Vec<Measurement> {
+        measurements
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !self.indexes.contains(i))
+            .map(|(_, &m)| m)
+            .collect()
+    }
+}
+
+/// Flag the measurements whose standardized throughput residuals, given the model, have an absolute
+/// value greater than `threshold`.
+///
+/// Residuals are standardized using a robust estimate of their standard deviation, so that the
+/// outliers themselves don't mask one another. A threshold of 3 is a common choice.
+#[must_use]
+pub fn detect(measurements: &[Measurement], model: &Model, threshold: f64) -> Outliers {
+    let residuals: Vec<f64> =
+        measurements.iter().map(|m| m.x - model.throughput_at_concurrency_f64(m.n)).collect();
+    let scale = robust_scale(&residuals);
+    let residuals: Vec<f64> = residuals.iter().map(|r| r / scale).collect();
+    let indexes = (0..residuals.len()).filter(|&i| residuals[i].abs() > threshold).collect();
+    Outliers { residuals, indexes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn detect_outliers() {
+        let mut measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        measurements[20] = Measurement::concurrency_and_throughput(21, 3000.0);
+        let model = Model::build_robust(&measurements);
+
+        let outliers = detect(&measurements, &model, 3.0);
+        assert_eq!(outliers.indexes, vec![20]);
+        assert_eq!(outliers.residuals.len(), 32);
+        assert!(outliers.residuals[20] < -3.0);
+
+        let clean = outliers.clean(&measurements);
+        assert_eq!(clean.len(), 31);
+        assert!(clean.iter().all(|m| m.x != 3000.0));
+    }
+}
@@ -0,0 +1,109 @@
+use crate::Model;
+
+/// The throughput lost to contention and coherency at a particular level of concurrency, relative
+/// to linear scaling.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PenaltyBreakdown {
+    /// The difference between linear throughput, `λN`, and the expected throughput, `X(N)`.
+    pub lost_throughput: f64,
+    /// The fraction of the lost throughput attributable to contention, the `σ(N-1)` term.
+    pub contention: f64,
+    /// The fraction of the lost throughput attributable to coherency, the `κN(N-1)` term.
+    pub coherency: f64,
+}
+
+/// The components of a model's expected mean latency at a particular level of concurrency, in
+/// seconds, via [Model::latency_components_at_concurrency].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LatencyComponents {
+    /// The base service time, `1/λ`.
+    pub service: f64,
+    /// The delay due to contention, `σ(N-1)/λ`.
+    pub contention: f64,
+    /// The delay due to coherency, `κN(N-1)/λ`.
+    pub coherency: f64,
+}
+
+impl Model {
+    /// Split the expected mean latency at a given number of concurrent events, `R(N)`, into the
+    /// base service time and the delays due to contention and coherency, which sum to `R(N)`.
+    #[must_use]
+    pub fn latency_components_at_concurrency(&self, n: u32) -> LatencyComponents {
+        let n: f64 = n.into();
+        LatencyComponents {
+            service: 1.0 / self.lambda,
+            contention: self.sigma * (n - 1.0) / self.lambda,
+            coherency: self.kappa * n * (n - 1.0) / self.lambda,
+        }
+    }
+
+    /// Break down the throughput lost at a given number of concurrent events into the fractions
+    /// attributable to contention and to coherency.
+    ///
+    /// Whichever fraction is larger indicates whether reducing serialization (σ) or crosstalk (κ)
+    /// would most improve throughput at that concurrency. If no throughput is lost, e.g. at a
+    /// concurrency of one, both fractions are zero.
+    #[must_use]
+    pub fn penalty_breakdown_at_concurrency(&self, n: u32) -> PenaltyBreakdown {
+        let n: f64 = n.into();
+        let contention = self.sigma * (n - 1.0);
+        let coherency = self.kappa * n * (n - 1.0);
+        let penalty = contention + coherency;
+        let (contention, coherency) =
+            if penalty == 0.0 { (0.0, 0.0) } else { (contention / penalty, coherency / penalty) };
+        PenaltyBreakdown {
+            lost_throughput: self.lambda * n - self.throughput_at_concurrency_f64(n),
+            contention,
+            coherency,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn penalty_breakdown_at_concurrency() {
+        let model: Model = MEASUREMENTS.iter().collect();
+
+        let breakdown = model.penalty_breakdown_at_concurrency(1);
+        assert_relative_eq!(breakdown.lost_throughput, 0.0, epsilon = 1e-9);
+        assert_relative_eq!(breakdown.contention, 0.0);
+        assert_relative_eq!(breakdown.coherency, 0.0);
+
+        let breakdown = model.penalty_breakdown_at_concurrency(20);
+        assert_relative_eq!(
+            breakdown.lost_throughput,
+            20.0 * model.lambda - model.throughput_at_concurrency(20)
+        );
+        assert_relative_eq!(breakdown.contention + breakdown.coherency, 1.0);
+        assert!(breakdown.contention > breakdown.coherency);
+
+        let breakdown = model.penalty_breakdown_at_concurrency(100);
+        assert!(breakdown.contention < breakdown.coherency);
+    }
+
+    #[test]
+    fn latency_components_at_concurrency() {
+        let model: Model = MEASUREMENTS.iter().collect();
+
+        let c = model.latency_components_at_concurrency(1);
+        assert_relative_eq!(c.service, model.latency_at_concurrency(1));
+        assert_relative_eq!(c.contention, 0.0);
+        assert_relative_eq!(c.coherency, 0.0);
+
+        let c = model.latency_components_at_concurrency(20);
+        assert_relative_eq!(c.service, 1.0 / model.lambda);
+        assert_relative_eq!(
+            c.service + c.contention + c.coherency,
+            model.latency_at_concurrency(20)
+        );
+        assert_relative_eq!(c.contention / c.coherency, model.sigma / (model.kappa * 20.0));
+    }
+}
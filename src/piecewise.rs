@@ -0,0 +1,178 @@
+use std::ops::Range;
+
+use crate::{Measurement, Model, ModelFitter, MIN_MEASUREMENTS};
+
+/// A model fitted to the measurements in one range of concurrency levels, via
+/// [Model::build_piecewise].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Piece {
+    /// The lowest measured concurrency in the segment.
+    pub start: f64,
+    /// The highest measured concurrency in the segment.
+    pub end: f64,
+    /// The model fitted to the measurements in the segment.
+    pub model: Model,
+}
+
+/// A set of models fitted to consecutive ranges of concurrency levels, via
+/// [Model::build_piecewise].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Piecewise {
+    /// The segments, in order of concurrency.
+    pub segments: Vec<Piece>,
+    /// The concurrency levels at which the system switches from one segment's model to the next,
+    /// halfway between the segments' measurements.
+    pub breakpoints: Vec<f64>,
+}
+
+impl Piecewise {
+    /// The model of the segment which covers the given concurrency.
+    #[must_use]
+    pub fn model_at(&self, n: f64) -> &Model {
+        let i = self.breakpoints.iter().take_while(|&&b| b <= n).count();
+        &self.segments[i].model
+    }
+
+    /// Calculate the expected throughput given a possibly fractional average number of concurrent
+    /// events, `X(N)`, using the model of the segment which covers it.
+    #[must_use]
+    pub fn throughput_at_concurrency_f64(&self, n: f64) -> f64 {
+        self.model_at(n).throughput_at_concurrency_f64(n)
+    }
+}
+
+impl Model {
+    /// Build a piecewise model for systems which switch regimes as concurrency increases, e.g.
+    /// after spilling to disk, rather than one fit which averages the regimes badly.
+    ///
+    /// Measurements are split by concurrency via binary segmentation: the split which most reduces
+    /// the total residual sum of squares is applied, as long as it improves the Bayesian
+    /// information criterion, until `max_breakpoints` splits have been made. Each segment has at
+    /// least [MIN_MEASUREMENTS] measurements. The criterion assumes the residuals have the same
+    /// variance at every concurrency.
+    #[must_use]
+    pub fn build_piecewise(measurements: &[Measurement], max_breakpoints: usize) -> Piecewise {
+        assert!(
+            measurements.len() >= MIN_MEASUREMENTS,
+            "must have at least {} measurements",
+            MIN_MEASUREMENTS
+        );
+        let mut sorted = measurements.to_vec();
+        sorted.sort_by(|a, b| a.n.total_cmp(&b.n));
+
+        let mut segments = vec![fit(&sorted).expect("unable to fit measurements")];
+        while segments.len() <= max_breakpoints {
+            let best = segments
+                .iter()
+                .enumerate()
+                .filter_map(|(i, s)| split(&sorted[s.range.clone()], s.range.start).map(|p| (i, p)))
+                .min_by(|(i, a), (j, b)| {
+                    (a.rss - segments[*i].rss).total_cmp(&(b.rss - segments[*j].rss))
+                });
+            let Some((i, split)) = best else { break };
+
+            let bic = |segments: usize, rss: f64| {
+                let n = sorted.len() as f64;
+                // Each segment has three parameters, and each breakpoint one more.
+                n * (rss / n).ln() + (4 * segments - 1) as f64 * n.ln()
+            };
+            let rss: f64 = segments.iter().map(|s| s.rss).sum();
+            if bic(segments.len() + 1, rss - segments[i].rss + split.rss)
+                >= bic(segments.len(), rss)
+            {
+                break;
+            }
+            segments.splice(i..=i, [split.left, split.right]);
+        }
+
+        Piecewise {
+            breakpoints: segments
+                .windows(2)
+                .map(|w| (sorted[w[0].range.end - 1].n + sorted[w[1].range.start].n) / 2.0)
+                .collect(),
+            segments: segments
+                .iter()
+                .map(|s| Piece {
+                    start: sorted[s.range.start].n,
+                    end: sorted[s.range.end - 1].n,
+                    model: s.model,
+                })
+                .collect(),
+        }
+    }
+}
+
+struct Fit {
+    range: Range<usize>,
+    model: Model,
+    rss: f64,
+}
+
+struct Split {
+    left: Fit,
+    right: Fit,
+    rss: f64,
+}
+
+fn fit(measurements: &[Measurement]) -> Option<Fit> {
+    let fitter = ModelFitter::new(measurements);
+    let model = Model::try_fit(&fitter, None).ok()?;
+    let rss =
+        measurements.iter().map(|m| (m.x - model.throughput_at_concurrency_f64(m.n)).powi(2)).sum();
+    Some(Fit { range: 0..measurements.len(), model, rss })
+}
+
+/// Find the split of the sorted measurements into two segments with the lowest total residual sum
+/// of squares, offsetting the segments' ranges by the given index.
+fn split(measurements: &[Measurement], offset: usize) -> Option<Split> {
+    (MIN_MEASUREMENTS..=measurements.len().saturating_sub(MIN_MEASUREMENTS))
+        // Don't split measurements taken at the same concurrency.
+        .filter(|&i| measurements[i - 1].n < measurements[i].n)
+        .filter_map(|i| {
+            let mut left = fit(&measurements[..i])?;
+            let mut right = fit(&measurements[i..])?;
+            left.range = offset..offset + i;
+            right.range = offset + i..offset + measurements.len();
+            Some(Split { rss: left.rss + right.rss, left, right })
+        })
+        .min_by(|a, b| a.rss.total_cmp(&b.rss))
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::NoiseSpec;
+
+    #[test]
+    fn build_piecewise() {
+        let memory = Model { sigma: 0.02, kappa: 0.0001, lambda: 1000.0 };
+        let disk = Model { sigma: 0.1, kappa: 0.0005, lambda: 800.0 };
+        let noise = NoiseSpec::Gaussian { sd: 50.0 };
+        let mut rng = StdRng::seed_from_u64(0xDEADBEEF);
+        let mut measurements = memory.generate(&(1..=20).collect::<Vec<u32>>(), noise, &mut rng);
+        measurements.extend(disk.generate(&(21..=40).collect::<Vec<u32>>(), noise, &mut rng));
+
+        let piecewise = Model::build_piecewise(&measurements, 2);
+        assert_eq!(piecewise.breakpoints, vec![20.5]);
+        assert_eq!(piecewise.segments.len(), 2);
+        assert_eq!((piecewise.segments[0].start, piecewise.segments[0].end), (1.0, 20.0));
+        assert_eq!((piecewise.segments[1].start, piecewise.segments[1].end), (21.0, 40.0));
+        assert_relative_eq!(
+            piecewise.throughput_at_concurrency_f64(30.0),
+            disk.throughput_at_concurrency(30),
+            max_relative = 0.01
+        );
+
+        let single = Model::build_piecewise(&measurements, 0);
+        assert!(single.breakpoints.is_empty());
+
+        let measurements = memory.generate(&(1..=40).collect::<Vec<u32>>(), noise, &mut rng);
+        assert_eq!(Model::build_piecewise(&measurements, 2).segments.len(), 1);
+    }
+}
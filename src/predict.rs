@@ -0,0 +1,200 @@
+use std::ops::RangeInclusive;
+
+use crate::{Bootstrap, Model, StandardErrors};
+
+/// A model's predictions at a particular number of concurrent events, via [Model::sweep] or
+/// [Model::predict_series].
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Prediction {
+    /// The number of concurrent events, `N`.
+    pub concurrency: u32,
+    /// The expected throughput, `X(N)`, in events/sec.
+    pub throughput: f64,
+    /// The expected mean latency, `R(N)`, in seconds.
+    pub latency: f64,
+    /// The fraction of ideal linear scaling retained, `E(N)`.
+    pub efficiency: f64,
+}
+
+/// The source of the uncertainty in a model's predictions, for [Model::sweep_with_bands].
+#[derive(Debug, Copy, Clone)]
+pub enum Uncertainty<'a> {
+    /// The standard errors of the model's parameters, e.g. from [Model::build_with_errors]. Bands
+    /// are derived via the delta method; see [Model::throughput_interval_at_concurrency].
+    Covariance(&'a StandardErrors),
+    /// Models fitted to bootstrap resamples of the model's measurements. Bands are percentile
+    /// intervals; see [Bootstrap::interval].
+    Bootstrap(&'a Bootstrap),
+}
+
+/// A model's predictions at a particular number of concurrent events, along with confidence bands,
+/// via [Model::sweep_with_bands].
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BandedPrediction {
+    /// The model's predictions.
+    pub prediction: Prediction,
+    /// The lower and upper bounds of the expected throughput.
+    pub throughput: (f64, f64),
+    /// The lower and upper bounds of the expected mean latency.
+    pub latency: (f64, f64),
+}
+
+impl Model {
+    /// Calculate the model's predictions at every `step`th number of concurrent events in the
+    /// given range, e.g. to tabulate or plot them.
+    ///
+    /// Efficiency is undefined at a concurrency of zero, and is `NaN` there.
+    ///
+    /// Panics if `step` is zero.
+    ///
+    /// ```
+    /// use usl::Model;
+    ///
+    /// let model = Model { sigma: 0.02, kappa: 0.0001, lambda: 1000.0 };
+    /// for p in model.sweep(1..=128, 16) {
+    ///     println!("{},{:.0},{:.6}", p.concurrency, p.throughput, p.latency);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn sweep(&self, range: RangeInclusive<u32>, step: u32) -> Vec<Prediction> {
+        assert!(step > 0, "step must be positive");
+        range.step_by(step as usize).map(|n| self.prediction(n)).collect()
+    }
+
+    /// Like [Model::sweep], but with lower and upper bounds on each prediction at the given
+    /// confidence level (e.g. `0.95`), derived from the given source of uncertainty.
+    ///
+    /// Panics if `step` is zero.
+    #[must_use]
+    pub fn sweep_with_bands(
+        &self,
+        range: RangeInclusive<u32>,
+        step: u32,
+        confidence: f64,
+        uncertainty: Uncertainty<'_>,
+    ) -> Vec<BandedPrediction> {
+        self.sweep(range, step)
+            .into_iter()
+            .map(|prediction| {
+                let n = prediction.concurrency;
+                let throughput = match uncertainty {
+                    Uncertainty::Covariance(errors) => {
+                        self.throughput_interval_at_concurrency(n, confidence, errors)
+                    }
+                    Uncertainty::Bootstrap(bootstrap) => {
+                        bootstrap.interval(confidence, |m| m.throughput_at_concurrency(n))
+                    }
+                };
+                // Latency is inversely proportional to throughput, so their bounds are swapped.
+                let n = f64::from(n);
+                BandedPrediction {
+                    prediction,
+                    throughput,
+                    latency: (n / throughput.1, n / throughput.0),
+                }
+            })
+            .collect()
+    }
+
+    /// Lazily calculate the model's predictions at each of the given numbers of concurrent events,
+    /// in order.
+    ///
+    /// Unlike [Model::sweep], the concurrency levels may be arbitrary, e.g. powers of two, and are
+    /// evaluated only as the returned iterator is consumed.
+    ///
+    /// ```
+    /// use usl::Model;
+    ///
+    /// let model = Model { sigma: 0.02, kappa: 0.0001, lambda: 1000.0 };
+    /// let peak = model
+    ///     .predict_series((0..20).map(|i| 1 << i))
+    ///     .max_by(|a, b| a.throughput.total_cmp(&b.throughput))
+    ///     .unwrap();
+    /// ```
+    pub fn predict_series<'a, I>(&'a self, concurrency: I) -> impl Iterator<Item = Prediction> + 'a
+    where
+        I: IntoIterator<Item = u32>,
+        I::IntoIter: 'a,
+    {
+        concurrency.into_iter().map(|n| self.prediction(n))
+    }
+
+    fn prediction(&self, n: u32) -> Prediction {
+        Prediction {
+            concurrency: n,
+            throughput: self.throughput_at_concurrency(n),
+            latency: self.latency_at_concurrency(n),
+            efficiency: self.efficiency_at_concurrency(n),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+    use crate::Measurement;
+
+    #[test]
+    fn sweep() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        let predictions = model.sweep(1..=32, 10);
+
+        assert_eq!(
+            predictions.iter().map(|p| p.concurrency).collect::<Vec<u32>>(),
+            vec![1, 11, 21, 31]
+        );
+        for p in &predictions {
+            assert_eq!(p.throughput, model.throughput_at_concurrency(p.concurrency));
+            assert_eq!(p.latency, model.latency_at_concurrency(p.concurrency));
+            assert_eq!(p.efficiency, model.efficiency_at_concurrency(p.concurrency));
+        }
+        assert_eq!(model.sweep(5..=5, 1).len(), 1);
+    }
+
+    #[test]
+    fn predict_series() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        let concurrency = [64, 1, 8, 1000];
+        let predictions: Vec<Prediction> = model.predict_series(concurrency).collect();
+
+        assert_eq!(predictions.len(), concurrency.len());
+        for (p, n) in predictions.iter().zip(concurrency) {
+            assert_eq!(*p, model.sweep(n..=n, 1)[0]);
+        }
+        assert_eq!(model.predict_series(1..).take(3).count(), 3);
+    }
+
+    #[test]
+    fn sweep_with_bands() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let (model, errors) = Model::build_with_errors(&measurements);
+
+        let bands = model.sweep_with_bands(1..=64, 8, 0.95, Uncertainty::Covariance(&errors));
+        assert_eq!(bands.len(), 8);
+        for b in &bands {
+            let p = b.prediction;
+            assert_eq!(
+                b.throughput,
+                model.throughput_interval_at_concurrency(p.concurrency, 0.95, &errors)
+            );
+            assert!(b.throughput.0 < p.throughput && p.throughput < b.throughput.1);
+            assert!(b.latency.0 < p.latency && p.latency < b.latency.1);
+        }
+
+        let mut rng = StdRng::seed_from_u64(0xDEADBEEF);
+        let bootstrap = Model::bootstrap_with_rng(&measurements, 100, &mut rng);
+        let bands = model.sweep_with_bands(8..=8, 1, 0.95, Uncertainty::Bootstrap(&bootstrap));
+        assert_eq!(
+            bands[0].throughput,
+            bootstrap.interval(0.95, |m| m.throughput_at_concurrency(8))
+        );
+        assert_relative_eq!(bands[0].latency.0, 8.0 / bands[0].throughput.1);
+    }
+}
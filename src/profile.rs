@@ -0,0 +1,204 @@
+use std::time::Duration;
+
+use crate::{FitOptions, FitTarget, Measurement, Model};
+
+/// A statistic of a system's latency distribution, via e.g. [LatencyProfile::get].
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LatencyStatistic {
+    /// The mean latency. This is the only statistic for which Little's Law holds.
+    #[default]
+    Mean,
+    /// The latency at the given quantile, e.g. `0.99`.
+    Quantile(f64),
+}
+
+impl LatencyStatistic {
+    /// The median latency.
+    pub const P50: LatencyStatistic = LatencyStatistic::Quantile(0.5);
+    /// The 90th percentile latency.
+    pub const P90: LatencyStatistic = LatencyStatistic::Quantile(0.9);
+    /// The 99th percentile latency.
+    pub const P99: LatencyStatistic = LatencyStatistic::Quantile(0.99);
+}
+
+/// The distribution of a system's latency at a single load level, in seconds.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LatencyProfile {
+    /// The mean latency.
+    pub mean: f64,
+    /// The median latency.
+    pub p50: f64,
+    /// The 90th percentile latency.
+    pub p90: f64,
+    /// The 99th percentile latency.
+    pub p99: f64,
+}
+
+impl LatencyProfile {
+    /// Create a latency profile from the given mean and percentile latencies.
+    #[must_use]
+    pub const fn new(
+        mean: Duration,
+        p50: Duration,
+        p90: Duration,
+        p99: Duration,
+    ) -> LatencyProfile {
+        LatencyProfile {
+            mean: mean.as_secs_f64(),
+            p50: p50.as_secs_f64(),
+            p90: p90.as_secs_f64(),
+            p99: p99.as_secs_f64(),
+        }
+    }
+
+    /// Return the given statistic of the latency distribution, in seconds, or `None` if it's a
+    /// quantile other than those in the profile.
+    #[must_use]
+    pub fn get(&self, statistic: LatencyStatistic) -> Option<f64> {
+        match statistic {
+            LatencyStatistic::Mean => Some(self.mean),
+            LatencyStatistic::Quantile(0.5) => Some(self.p50),
+            LatencyStatistic::Quantile(0.9) => Some(self.p90),
+            LatencyStatistic::Quantile(0.99) => Some(self.p99),
+            LatencyStatistic::Quantile(_) => None,
+        }
+    }
+}
+
+/// A [Measurement] whose latency is described by a full [LatencyProfile] rather than only its
+/// mean.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProfiledMeasurement {
+    /// The measurement, whose latency is the profile's mean.
+    pub measurement: Measurement,
+    /// The distribution of latencies.
+    pub latency: LatencyProfile,
+}
+
+impl ProfiledMeasurement {
+    /// Create a measurement of a system's latency distribution at a given level of concurrency.
+    /// The throughput of the system is derived from the mean latency via Little's Law.
+    #[must_use]
+    pub fn concurrency_and_latency(n: u32, latency: LatencyProfile) -> ProfiledMeasurement {
+        let n = n.into();
+        ProfiledMeasurement {
+            measurement: Measurement { n, x: n / latency.mean, r: latency.mean },
+            latency,
+        }
+    }
+
+    /// Create a measurement of a system's latency distribution at a given level of throughput.
+    /// The concurrency of the system is derived from the mean latency via Little's Law.
+    #[must_use]
+    pub fn throughput_and_latency(x: f64, latency: LatencyProfile) -> ProfiledMeasurement {
+        ProfiledMeasurement {
+            measurement: Measurement { n: x * latency.mean, x, r: latency.mean },
+            latency,
+        }
+    }
+
+    /// Return a measurement whose latency is the given statistic of the latency distribution, and
+    /// whose throughput is derived from it via Little's Law.
+    ///
+    /// Little's Law only holds for mean latencies, so for any other statistic the resulting
+    /// throughput is a notional value rather than the system's actual throughput. Returns `None`
+    /// if the statistic is a quantile other than those in the [LatencyProfile].
+    #[must_use]
+    pub fn at(&self, statistic: LatencyStatistic) -> Option<Measurement> {
+        let n = self.measurement.n;
+        let r = self.latency.get(statistic)?;
+        Some(Measurement { n, x: n / r, r })
+    }
+}
+
+impl Model {
+    /// Build a model of the given latency statistic from measurements of a system's latency
+    /// distribution.
+    ///
+    /// The model is fitted to the residuals of the chosen statistic at each measured concurrency
+    /// level (as with [FitTarget::Latency]), so [Model::latency_at_concurrency] predicts that
+    /// statistic and values derived from it (e.g. [Model::concurrency_at_latency]) describe its
+    /// behavior. For any statistic other than [LatencyStatistic::Mean], the model's throughput
+    /// predictions are notional, and λ is the reciprocal of the statistic's latency with a single
+    /// concurrent event.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use usl::{LatencyProfile, LatencyStatistic, Model, ProfiledMeasurement};
+    ///
+    /// let ms = |v: u64| Duration::from_millis(v);
+    /// let measurements: Vec<ProfiledMeasurement> = [
+    ///     (1, LatencyProfile::new(ms(10), ms(9), ms(15), ms(30))),
+    ///     (2, LatencyProfile::new(ms(11), ms(10), ms(16), ms(33))),
+    ///     (4, LatencyProfile::new(ms(12), ms(11), ms(18), ms(38))),
+    ///     (8, LatencyProfile::new(ms(15), ms(13), ms(23), ms(48))),
+    ///     (16, LatencyProfile::new(ms(21), ms(18), ms(32), ms(70))),
+    ///     (32, LatencyProfile::new(ms(35), ms(30), ms(55), ms(120))),
+    /// ]
+    /// .iter()
+    /// .map(|&(n, latency)| ProfiledMeasurement::concurrency_and_latency(n, latency))
+    /// .collect();
+    /// let model = Model::build_percentile(&measurements, LatencyStatistic::P99);
+    /// let n = model.concurrency_at_latency(Duration::from_millis(100));
+    /// println!("p99 stays under 100ms up to {} concurrent events", n);
+    /// ```
+    ///
+    /// Panics if the statistic is a quantile other than those in [LatencyProfile].
+    #[must_use]
+    pub fn build_percentile(
+        measurements: &[ProfiledMeasurement],
+        statistic: LatencyStatistic,
+    ) -> Model {
+        let measurements: Vec<Measurement> = measurements
+            .iter()
+            .map(|m| m.at(statistic).expect("latency profiles have no such quantile"))
+            .collect();
+        let options = FitOptions { target: FitTarget::Latency, ..Default::default() };
+        Model::build_with_options(&measurements, &options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn build_percentile() {
+        let measurements: Vec<ProfiledMeasurement> = MEASUREMENTS
+            .iter()
+            .map(|&(n, x)| {
+                let r = n as f64 / x;
+                let latency = LatencyProfile { mean: r, p50: 0.8 * r, p90: 2.0 * r, p99: 4.0 * r };
+                ProfiledMeasurement::throughput_and_latency(x, latency)
+            })
+            .collect();
+        let means: Vec<Measurement> = measurements.iter().map(|m| m.measurement).collect();
+        let options = FitOptions { target: FitTarget::Latency, ..Default::default() };
+        let mean = Model::build_with_options(&means, &options);
+
+        let model = Model::build_percentile(&measurements, LatencyStatistic::Mean);
+        assert_relative_eq!(model.sigma, mean.sigma, max_relative = 0.0001);
+        assert_relative_eq!(model.kappa, mean.kappa, max_relative = 0.0001);
+        assert_relative_eq!(model.lambda, mean.lambda, max_relative = 0.0001);
+
+        // Scaling every latency by a constant leaves the shape of the curve unchanged.
+        let p99 = Model::build_percentile(&measurements, LatencyStatistic::P99);
+        assert_relative_eq!(p99.sigma, mean.sigma, max_relative = 0.001);
+        assert_relative_eq!(p99.kappa, mean.kappa, max_relative = 0.001);
+        assert_relative_eq!(p99.lambda, mean.lambda / 4.0, max_relative = 0.001);
+        assert_relative_eq!(
+            p99.latency_at_concurrency(20),
+            4.0 * mean.latency_at_concurrency(20),
+            max_relative = 0.001
+        );
+
+        assert_eq!(measurements[0].latency.get(LatencyStatistic::Quantile(0.95)), None);
+        assert!(measurements[0].at(LatencyStatistic::P50).is_some());
+    }
+}
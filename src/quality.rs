@@ -0,0 +1,74 @@
+use crate::{Measurement, Model, ScalabilityModel};
+
+/// Goodness-of-fit metrics comparing a model's predicted throughput with observed throughput.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FitQuality {
+    /// The coefficient of determination, R².
+    pub r_squared: f64,
+    /// R² adjusted for the model's number of non-zero coefficients, per
+    /// [ScalabilityModel::parameters], which is fewer than three for models built with
+    /// coefficients pinned at zero, e.g. via [Model::build_amdahl].
+    pub adjusted_r_squared: f64,
+    /// The root mean squared error.
+    pub rmse: f64,
+    /// The mean absolute error.
+    pub mae: f64,
+}
+
+impl Model {
+    /// Calculate goodness-of-fit metrics for the model's predicted throughput at each of the given
+    /// measurements' concurrency levels.
+    #[must_use]
+    pub fn fit_quality(&self, measurements: &[Measurement]) -> FitQuality {
+        let n = measurements.len() as f64;
+        let mean = measurements.iter().map(|m| m.x).sum::<f64>() / n;
+        let residuals: Vec<f64> =
+            measurements.iter().map(|m| m.x - self.throughput_at_concurrency_f64(m.n)).collect();
+
+        let ss_res: f64 = residuals.iter().map(|r| r * r).sum();
+        let ss_tot: f64 = measurements.iter().map(|m| (m.x - mean).powi(2)).sum();
+        let r_squared = 1.0 - ss_res / ss_tot;
+        let k = self.parameters() as f64;
+        FitQuality {
+            r_squared,
+            adjusted_r_squared: 1.0 - (1.0 - r_squared) * (n - 1.0) / (n - k),
+            rmse: (ss_res / n).sqrt(),
+            mae: residuals.iter().map(|r| r.abs()).sum::<f64>() / n,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn fit_quality() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let model = Model::build(&measurements);
+        let quality = model.fit_quality(&measurements);
+
+        assert!(quality.r_squared > 0.99 && quality.r_squared < 1.0);
+        assert!(quality.adjusted_r_squared < quality.r_squared);
+        assert!(quality.mae > 0.0 && quality.mae <= quality.rmse);
+
+        let perfect: Vec<Measurement> = (1..=10)
+            .map(|n| Measurement::concurrency_and_throughput(n, model.throughput_at_concurrency(n)))
+            .collect();
+        let quality = model.fit_quality(&perfect);
+        assert_relative_eq!(quality.r_squared, 1.0);
+        assert_relative_eq!(quality.rmse, 0.0);
+
+        let amdahl = Model::build_amdahl(&measurements);
+        let quality = amdahl.fit_quality(&measurements);
+        let n = measurements.len() as f64;
+        assert_relative_eq!(
+            quality.adjusted_r_squared,
+            1.0 - (1.0 - quality.r_squared) * (n - 1.0) / (n - 2.0)
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use rand::seq::index::sample;
+use rand::Rng;
+
+use crate::{Measurement, Model, ModelFitter, MIN_MEASUREMENTS};
+
+/// Options for building a model via [Model::build_ransac].
+#[derive(Debug, Copy, Clone)]
+pub struct RansacOptions {
+    /// The number of random subsets to fit.
+    pub iterations: usize,
+    /// The number of measurements in each random subset. Must be at least [MIN_MEASUREMENTS].
+    pub sample_size: usize,
+    /// The maximum relative error between a measurement's observed and predicted throughput for
+    /// it to be considered an inlier.
+    pub threshold: f64,
+}
+
+impl Default for RansacOptions {
+    fn default() -> Self {
+        RansacOptions { iterations: 100, sample_size: MIN_MEASUREMENTS, threshold: 0.1 }
+    }
+}
+
+/// The result of building a model via [Model::build_ransac].
+#[derive(Debug, Clone)]
+pub struct Ransac {
+    /// The model fitted to the consensus set of inliers.
+    pub model: Model,
+    /// The measurements which were rejected as outliers.
+    pub outliers: Vec<Measurement>,
+}
+
+impl Model {
+    /// Build a model which tolerates a large fraction of corrupted measurements.
+    ///
+    /// Repeatedly fits models to random subsets of the measurements, keeping the model which has
+    /// the most measurements within [RansacOptions::threshold] of its predictions. The returned
+    /// model is fitted to that consensus set, and the remaining measurements are returned as
+    /// outliers.
+    ///
+    /// Panics if no model is found which has at least [MIN_MEASUREMENTS] inliers.
+    #[must_use]
+    pub fn build_ransac(measurements: &[Measurement], options: &RansacOptions) -> Ransac {
+        Model::build_ransac_with_rng(measurements, options, &mut rand::thread_rng())
+    }
+
+    /// Like [Model::build_ransac], but using the given random number generator.
+    ///
+    /// Passing a seeded generator, e.g. `StdRng::seed_from_u64`, makes the results reproducible.
+    #[must_use]
+    pub fn build_ransac_with_rng(
+        measurements: &[Measurement],
+        options: &RansacOptions,
+        rng: &mut impl Rng,
+    ) -> Ransac {
+        assert!(
+            options.sample_size >= MIN_MEASUREMENTS,
+            "sample size must be at least {}",
+            MIN_MEASUREMENTS
+        );
+        assert!(
+            measurements.len() >= options.sample_size,
+            "must have at least {} measurements",
+            options.sample_size
+        );
+
+        let mut best: Option<(Vec<bool>, f64)> = None;
+        for _ in 0..options.iterations {
+            let subset: Vec<Measurement> = sample(rng, measurements.len(), options.sample_size)
+                .into_iter()
+                .map(|i| measurements[i])
+                .collect();
+            let model = match Model::try_fit(&ModelFitter::new(&subset), None) {
+                Ok(model) => model,
+                Err(_) => continue,
+            };
+
+            let mut error = 0.0;
+            let inliers: Vec<bool> = measurements
+                .iter()
+                .map(|m| {
+                    let e = ((m.x - model.throughput_at_concurrency_f64(m.n)) / m.x).abs();
+                    let inlier = e <= options.threshold;
+                    if inlier {
+                        error += e * e;
+                    }
+                    inlier
+                })
+                .collect();
+
+            let count = inliers.iter().filter(|&&i| i).count();
+            let better = match &best {
+                None => true,
+                Some((b, e)) => {
+                    let n = b.iter().filter(|&&i| i).count();
+                    count > n || (count == n && error < *e)
+                }
+            };
+            if count >= MIN_MEASUREMENTS && better {
+                best = Some((inliers, error));
+            }
+        }
+
+        let (inliers, _) = best.expect("no consensus found");
+        let (consensus, outliers): (Vec<_>, Vec<_>) =
+            measurements.iter().zip(inliers).partition(|&(_, inlier)| inlier);
+        Ransac {
+            model: Model::build(&consensus.into_iter().map(|(&m, _)| m).collect::<Vec<_>>()),
+            outliers: outliers.into_iter().map(|(&m, _)| m).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn build_ransac() {
+        let expected: Model = MEASUREMENTS.iter().collect();
+
+        let mut measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        measurements[3] = Measurement::concurrency_and_throughput(4, 9000.0);
+        measurements[20] = Measurement::concurrency_and_throughput(21, 3000.0);
+
+        let mut rng = StdRng::seed_from_u64(0xDEADBEEF);
+        let ransac =
+            Model::build_ransac_with_rng(&measurements, &RansacOptions::default(), &mut rng);
+
+        let outliers: Vec<f64> = ransac.outliers.iter().map(|m| m.n).collect();
+        assert_eq!(outliers, vec![4.0, 21.0]);
+        assert_relative_eq!(ransac.model.kappa, expected.kappa, max_relative = 0.05);
+        assert_relative_eq!(ransac.model.sigma, expected.sigma, max_relative = 0.05);
+        assert_relative_eq!(ransac.model.lambda, expected.lambda, max_relative = 0.05);
+    }
+}
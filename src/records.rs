@@ -0,0 +1,219 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::{Dataset, Measurement};
+
+/// A record of a single request served by a system, for [Dataset::from_requests].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RequestRecord {
+    /// The time at which the request arrived, relative to an arbitrary epoch.
+    pub arrival: Duration,
+    /// How long the request took to serve.
+    pub duration: Duration,
+}
+
+impl Dataset {
+    /// Build a dataset from records of individual requests, with one measurement for each window
+    /// of the given width in which at least one request completed.
+    ///
+    /// Windows are aligned to multiples of the width. Each window's throughput is the number of
+    /// requests which completed in it, divided by its width. Its concurrency is the time-average
+    /// number of requests in progress during it, counting only the part of each request which
+    /// overlapped it, so long requests which span several windows contribute to each of them.
+    /// (Averaging the concurrency of only the requests which completed in a window overestimates
+    /// it.) Its latency is derived from the two via Little's Law.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use usl::{Dataset, RequestRecord};
+    ///
+    /// let records = (0..1000).map(|i| RequestRecord {
+    ///     arrival: Duration::from_millis(i * 10),
+    ///     duration: Duration::from_millis(5),
+    /// });
+    /// let dataset = Dataset::from_requests(records, Duration::from_secs(1));
+    /// assert_eq!(dataset.len(), 10);
+    /// ```
+    #[must_use]
+    pub fn from_requests(
+        records: impl IntoIterator<Item = RequestRecord>,
+        width: Duration,
+    ) -> Dataset {
+        let mut bins = Bins::new(width);
+        for record in records {
+            let end = record.arrival + record.duration;
+            bins.add_busy(record.arrival, end, 1.0);
+            bins.add_completion(end);
+        }
+        bins.into_dataset()
+    }
+}
+
+impl Dataset {
+    /// Build a dataset from separate streams of the times at which requests started and
+    /// completed, e.g. from queue worker logs or tracing spans, with one measurement for each
+    /// window of the given width in which at least one request completed.
+    ///
+    /// The concurrency of the system over time is reconstructed from the events, and is otherwise
+    /// treated as in [Dataset::from_requests]. The streams need not be sorted or paired. A
+    /// completion with no earlier unmatched start, e.g. of a request which started before the logs
+    /// begin, counts towards throughput but not concurrency.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use usl::Dataset;
+    ///
+    /// let starts = (0..100).map(|i| Duration::from_millis(i * 100));
+    /// let ends = (0..100).map(|i| Duration::from_millis(i * 100 + 40));
+    /// let dataset = Dataset::from_events(starts, ends, Duration::from_secs(1));
+    /// assert_eq!(dataset.len(), 10);
+    /// ```
+    #[must_use]
+    pub fn from_events(
+        starts: impl IntoIterator<Item = Duration>,
+        ends: impl IntoIterator<Item = Duration>,
+        width: Duration,
+    ) -> Dataset {
+        // Sort completions before starts at the same time, so an instantaneous handoff between
+        // requests doesn't count as both being in progress.
+        let mut events: Vec<(Duration, bool)> = starts
+            .into_iter()
+            .map(|t| (t, true))
+            .chain(ends.into_iter().map(|t| (t, false)))
+            .collect();
+        events.sort();
+
+        let mut bins = Bins::new(width);
+        let mut in_progress = 0u64;
+        let mut last = Duration::ZERO;
+        for (t, start) in events {
+            if in_progress > 0 {
+                bins.add_busy(last, t, in_progress as f64);
+            }
+            if start {
+                in_progress += 1;
+            } else {
+                in_progress = in_progress.saturating_sub(1);
+                bins.add_completion(t);
+            }
+            last = t;
+        }
+        bins.into_dataset()
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+struct Bin {
+    /// The integral of concurrency over the bin, in seconds.
+    busy: f64,
+    completions: u64,
+}
+
+/// Accumulates the busy time and completions of a system in fixed windows of time.
+struct Bins {
+    width: f64,
+    bins: BTreeMap<u64, Bin>,
+}
+
+impl Bins {
+    fn new(width: Duration) -> Bins {
+        assert!(!width.is_zero(), "width must be non-zero");
+        Bins { width: width.as_secs_f64(), bins: BTreeMap::new() }
+    }
+
+    fn index(&self, t: Duration) -> u64 {
+        (t.as_secs_f64() / self.width).floor() as u64
+    }
+
+    /// Add the given concurrency over the interval from `start` to `end`.
+    fn add_busy(&mut self, start: Duration, end: Duration, concurrency: f64) {
+        let (start_secs, end_secs) = (start.as_secs_f64(), end.as_secs_f64());
+        for i in self.index(start)..=self.index(end) {
+            let (bin_start, bin_end) = (i as f64 * self.width, (i + 1) as f64 * self.width);
+            let overlap = end_secs.min(bin_end) - start_secs.max(bin_start);
+            if overlap > 0.0 {
+                self.bins.entry(i).or_default().busy += overlap * concurrency;
+            }
+        }
+    }
+
+    fn add_completion(&mut self, t: Duration) {
+        self.bins.entry(self.index(t)).or_default().completions += 1;
+    }
+
+    fn into_dataset(self) -> Dataset {
+        self.bins
+            .values()
+            .filter(|b| b.completions > 0)
+            .map(|b| {
+                let n = b.busy / self.width;
+                let x = b.completions as f64 / self.width;
+                Measurement { n, x, r: n / x }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn from_requests() {
+        let record = |arrival: u64, duration: u64| RequestRecord {
+            arrival: Duration::from_millis(arrival),
+            duration: Duration::from_millis(duration),
+        };
+        let records = [
+            record(0, 500),
+            record(250, 500),
+            // Spans the first two windows, completing in the second.
+            record(500, 1000),
+            record(1100, 200),
+            // Completes in the third window, which has no other activity.
+            record(2000, 100),
+        ];
+
+        let dataset = Dataset::from_requests(records, Duration::from_secs(1));
+        assert_eq!(dataset.len(), 3);
+
+        // 0.5s + 0.5s + 0.5s busy in the first second, with two completions.
+        assert_relative_eq!(dataset[0].n, 1.5);
+        assert_relative_eq!(dataset[0].x, 2.0);
+        assert_relative_eq!(dataset[0].r, 0.75);
+
+        // 0.5s + 0.2s busy in the second second, with two completions.
+        assert_relative_eq!(dataset[1].n, 0.7);
+        assert_relative_eq!(dataset[1].x, 2.0);
+
+        assert_relative_eq!(dataset[2].n, 0.1);
+        assert_relative_eq!(dataset[2].x, 1.0);
+    }
+
+    #[test]
+    fn from_events() {
+        let ms = Duration::from_millis;
+        let records = [(0, 500), (250, 500), (500, 1000), (1100, 200), (2000, 100)];
+        let starts = records.iter().map(|&(a, _)| ms(a)).rev();
+        let ends = records.iter().map(|&(a, d)| ms(a + d));
+
+        let dataset = Dataset::from_events(starts, ends, Duration::from_secs(1));
+        let expected = Dataset::from_requests(
+            records.iter().map(|&(a, d)| RequestRecord { arrival: ms(a), duration: ms(d) }),
+            Duration::from_secs(1),
+        );
+        assert_eq!(dataset.len(), expected.len());
+        for (a, b) in dataset.iter().zip(expected.iter()) {
+            assert_relative_eq!(a.n, b.n, epsilon = 1e-12);
+            assert_relative_eq!(a.x, b.x);
+        }
+
+        // A completion without a start counts only towards throughput.
+        let dataset = Dataset::from_events([ms(100)], [ms(50), ms(300)], Duration::from_secs(1));
+        assert_relative_eq!(dataset[0].n, 0.2);
+        assert_relative_eq!(dataset[0].x, 2.0);
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{map_fits, BuildError, Dataset, Measurement, Metadata, Model};
+
+/// A [Measurement] tagged with the benchmark run which produced it.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TaggedMeasurement {
+    /// The run which produced the measurement.
+    pub run: String,
+    /// The measurement.
+    pub measurement: Measurement,
+}
+
+/// Measurements from several benchmark runs of the same system, each tagged with its run.
+///
+/// ```
+/// use usl::{Dataset, Metadata, Runs};
+///
+/// let dataset = |run: &str, scale: f64| {
+///     [(1, 65.0), (18, 996.0), (36, 1652.0), (72, 1853.0), (108, 1829.0), (144, 1775.0)]
+///         .iter()
+///         .map(|&(n, x)| (n, x * scale).into())
+///         .collect::<Dataset>()
+///         .with_metadata(Metadata { run_id: Some(run.into()), ..Default::default() })
+/// };
+/// let runs: Runs = [dataset("tuesday", 1.0), dataset("thursday", 1.1)].into_iter().collect();
+/// let pooled = runs.build_pooled();
+/// for (run, model) in runs.build_per_run() {
+///     println!("{}: X{{max}}={}", run, model.max_throughput());
+/// }
+/// ```
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Runs {
+    measurements: Vec<TaggedMeasurement>,
+}
+
+impl Runs {
+    /// Create an empty set of runs.
+    #[must_use]
+    pub const fn new() -> Runs {
+        Runs { measurements: Vec::new() }
+    }
+
+    /// Add the measurements of a dataset, tagged with the given run.
+    pub fn push(&mut self, run: impl Into<String>, dataset: &Dataset) {
+        let run = run.into();
+        self.measurements.extend(
+            dataset.iter().map(|&measurement| TaggedMeasurement { run: run.clone(), measurement }),
+        );
+    }
+
+    /// The tagged measurements, in the order they were added.
+    #[must_use]
+    pub fn measurements(&self) -> &[TaggedMeasurement] {
+        &self.measurements
+    }
+
+    /// The distinct runs, in the order they were added.
+    #[must_use]
+    pub fn runs(&self) -> Vec<&str> {
+        let mut runs: Vec<&str> = Vec::new();
+        for m in &self.measurements {
+            if !runs.contains(&m.run.as_str()) {
+                runs.push(&m.run);
+            }
+        }
+        runs
+    }
+
+    /// The measurements from the given run, with the run as the dataset's run ID.
+    #[must_use]
+    pub fn run(&self, run: &str) -> Dataset {
+        self.measurements
+            .iter()
+            .filter(|m| m.run == run)
+            .map(|m| m.measurement)
+            .collect::<Dataset>()
+            .with_metadata(Metadata { run_id: Some(run.into()), ..Default::default() })
+    }
+
+    /// The measurements from every run, pooled into a single dataset.
+    #[must_use]
+    pub fn pooled(&self) -> Dataset {
+        self.measurements.iter().map(|m| m.measurement).collect()
+    }
+
+    /// Build a model from the measurements of every run, pooled together. This assumes the runs
+    /// measured the same system under the same conditions, which can be checked by comparing
+    /// each pair of runs via [compare::significance](crate::compare::significance).
+    #[must_use]
+    pub fn build_pooled(&self) -> Model {
+        Model::build(&self.pooled())
+    }
+
+    /// Build a model from the measurements of each run, in the order the runs were added.
+    ///
+    /// Panics if any run can't be fitted, as with [Model::build].
+    #[must_use]
+    pub fn build_per_run(&self) -> Vec<(String, Model)> {
+        self.try_build_per_run()
+            .into_iter()
+            .map(|(run, model)| {
+                let model = model.unwrap_or_else(|err| panic!("run {:?}: {}", run, err));
+                (run, model)
+            })
+            .collect()
+    }
+
+    /// Build a model from the measurements of each run as with [Runs::build_per_run], returning
+    /// an error for each run which can't be fitted instead of panicking.
+    #[must_use]
+    pub fn try_build_per_run(&self) -> Vec<(String, Result<Model, BuildError>)> {
+        let runs: Vec<(String, Dataset)> =
+            self.runs().into_iter().map(|run| (run.to_string(), self.run(run))).collect();
+        map_fits(&runs, |(run, dataset)| (run.clone(), Model::try_build(dataset)))
+    }
+}
+
+/// Merges datasets, tagging each with its run ID, or with its position if it has none.
+impl FromIterator<Dataset> for Runs {
+    fn from_iter<T: IntoIterator<Item = Dataset>>(iter: T) -> Self {
+        let mut runs = Runs::new();
+        runs.extend(iter);
+        runs
+    }
+}
+
+/// Merges datasets, tagging each with its run ID, or with its position if it has none.
+impl Extend<Dataset> for Runs {
+    fn extend<T: IntoIterator<Item = Dataset>>(&mut self, iter: T) {
+        let offset = self.runs().len();
+        for (i, dataset) in iter.into_iter().enumerate() {
+            let run = dataset.metadata().run_id.clone().unwrap_or_else(|| (offset + i).to_string());
+            self.push(run, &dataset);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn runs() {
+        let tuesday: Dataset = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let thursday: Dataset = MEASUREMENTS.iter().map(|&(n, x)| (n, x * 2.0).into()).collect();
+        let tuesday = tuesday
+            .with_metadata(Metadata { run_id: Some("tuesday".into()), ..Default::default() });
+
+        let mut runs: Runs = [tuesday.clone(), thursday].into_iter().collect();
+        assert_eq!(runs.runs(), vec!["tuesday", "1"]);
+        assert_eq!(runs.measurements().len(), 64);
+        assert_eq!(runs.run("1").len(), 32);
+        assert_eq!(runs.run("1").metadata().run_id.as_deref(), Some("1"));
+        assert_eq!(runs.pooled().len(), 64);
+
+        let models = runs.build_per_run();
+        assert_eq!(models[0].0, "tuesday");
+        assert_relative_eq!(models[0].1.lambda, 995.6486, max_relative = 0.0001);
+        assert_relative_eq!(models[1].1.lambda, 2.0 * 995.6486, max_relative = 0.0001);
+        assert_relative_eq!(models[1].1.kappa, models[0].1.kappa, max_relative = 0.0001);
+
+        let pooled = runs.build_pooled();
+        assert!(pooled.lambda > models[0].1.lambda && pooled.lambda < models[1].1.lambda);
+
+        runs.push("friday", &Dataset::new(tuesday[..3].to_vec()));
+        let results = runs.try_build_per_run();
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[2], (ref run, Err(_)) if run == "friday"));
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{Model, StandardErrors};
+
+/// The sensitivity of a model's predicted throughput to each of its parameters, via
+/// [Model::sensitivity_at_concurrency].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sensitivity {
+    /// The partial derivative of throughput with respect to σ, `∂X/∂σ`.
+    pub sigma: f64,
+    /// The partial derivative of throughput with respect to κ, `∂X/∂κ`.
+    pub kappa: f64,
+    /// The partial derivative of throughput with respect to λ, `∂X/∂λ`.
+    pub lambda: f64,
+}
+
+impl Sensitivity {
+    /// Scale each partial derivative by the standard error of its parameter, giving the standard
+    /// deviation of the predicted throughput due to the uncertainty in each parameter alone.
+    ///
+    /// The largest value indicates which parameter's uncertainty dominates the prediction.
+    #[must_use]
+    pub fn uncertainty(&self, errors: &StandardErrors) -> Sensitivity {
+        Sensitivity {
+            sigma: (self.sigma * errors.sigma).abs(),
+            kappa: (self.kappa * errors.kappa).abs(),
+            lambda: (self.lambda * errors.lambda).abs(),
+        }
+    }
+}
+
+impl Model {
+    /// Calculate the partial derivatives of the expected throughput at a given number of
+    /// concurrent events, `X(N)`, with respect to σ, κ, and λ.
+    #[must_use]
+    pub fn sensitivity_at_concurrency(&self, n: u32) -> Sensitivity {
+        let [sigma, kappa, lambda] = self.parameter_gradient(n.into());
+        Sensitivity { sigma, kappa, lambda }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+    use crate::Measurement;
+
+    #[test]
+    fn sensitivity_at_concurrency() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        let h = 1e-7;
+        for n in [1, 10, 35] {
+            let s = model.sensitivity_at_concurrency(n);
+            let x = model.throughput_at_concurrency(n);
+            let diff = |m: Model| (m.throughput_at_concurrency(n) - x) / h;
+            assert_relative_eq!(
+                s.sigma,
+                diff(model.with_sigma(model.sigma + h)),
+                epsilon = 1e-2,
+                max_relative = 1e-4
+            );
+            assert_relative_eq!(
+                s.kappa,
+                diff(model.with_kappa(model.kappa + h)),
+                epsilon = 1e-2,
+                max_relative = 1e-4
+            );
+            assert_relative_eq!(
+                s.lambda,
+                diff(model.with_lambda(model.lambda + h)),
+                epsilon = 1e-2,
+                max_relative = 1e-4
+            );
+        }
+
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let (model, errors) = Model::build_with_errors(&measurements);
+        let u = model.sensitivity_at_concurrency(20).uncertainty(&errors);
+        assert!(u.sigma > 0.0 && u.kappa > 0.0 && u.lambda > 0.0);
+    }
+}
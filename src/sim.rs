@@ -0,0 +1,212 @@
+//! Monte Carlo simulation of closed systems described by a model.
+//!
+//! A model predicts the mean throughput and latency of a system at a fixed concurrency. A load
+//! test, on the other hand, drives the system with a fixed number of clients, each of which waits
+//! for a response and then thinks for a while before sending its next request. The concurrency
+//! the system sees varies from moment to moment, and any finite test observes a noisy sample of
+//! its behavior. Simulating such a test shows how much its results can be expected to vary.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::{Model, Spread};
+
+/// The parameters of a simulated load test, via [simulate].
+#[derive(Debug, Copy, Clone)]
+pub struct Options {
+    /// The number of clients, each of which has at most one outstanding request.
+    pub clients: u32,
+    /// The mean time each client waits between receiving a response and sending its next request.
+    /// Think times are exponentially distributed.
+    pub think_time: Duration,
+    /// The number of requests completed in each run, after warming up.
+    pub requests: usize,
+    /// The number of requests completed at the start of each run which are not measured.
+    pub warm_up: usize,
+    /// The number of independent runs.
+    pub runs: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            clients: 32,
+            think_time: Duration::ZERO,
+            requests: 10_000,
+            warm_up: 1_000,
+            runs: 20,
+        }
+    }
+}
+
+/// The observed results of a single simulated load test.
+#[derive(Debug, Copy, Clone)]
+pub struct Run {
+    /// The throughput of the system, in events/sec.
+    pub throughput: f64,
+    /// The mean latency of the system's responses, in seconds, excluding think time.
+    pub latency: f64,
+    /// The mean number of requests in the system.
+    pub concurrency: f64,
+}
+
+/// The results of a set of simulated load tests.
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    /// The results of each run.
+    pub runs: Vec<Run>,
+}
+
+impl Simulation {
+    /// Calculate the spread of the observed throughput across runs.
+    #[must_use]
+    pub fn throughput(&self) -> Spread {
+        Spread::of(&self.runs.iter().map(|r| r.throughput).collect::<Vec<f64>>())
+    }
+
+    /// Calculate the spread of the observed mean latency across runs.
+    #[must_use]
+    pub fn latency(&self) -> Spread {
+        Spread::of(&self.runs.iter().map(|r| r.latency).collect::<Vec<f64>>())
+    }
+}
+
+/// Simulate load tests of the system described by the given model.
+///
+/// The system is treated as a single server whose service rate, when it holds `n` requests, is
+/// the model's predicted throughput at concurrency `n`. Service and think times are exponentially
+/// distributed.
+///
+/// Panics if there are no clients, no requests, or no runs.
+///
+/// ```
+/// use std::time::Duration;
+/// use usl::Model;
+/// use usl::sim::{simulate, Options};
+///
+/// let model = Model { sigma: 0.02, kappa: 0.0001, lambda: 1000.0 };
+/// let options = Options { clients: 64, think_time: Duration::from_millis(10), ..Default::default() };
+/// let x = simulate(&model, &options).throughput();
+/// println!("X = {:.0} ± {:.0}", x.mean, x.std_dev);
+/// ```
+#[must_use]
+pub fn simulate(model: &Model, options: &Options) -> Simulation {
+    simulate_with_rng(model, options, &mut rand::thread_rng())
+}
+
+/// Like [simulate], but using the given random number generator.
+///
+/// Passing a seeded generator, e.g. `StdRng::seed_from_u64`, makes the results reproducible.
+#[must_use]
+pub fn simulate_with_rng(model: &Model, options: &Options, rng: &mut impl Rng) -> Simulation {
+    assert!(options.clients > 0, "must have at least one client");
+    assert!(options.requests > 0, "must have at least one request");
+    assert!(options.runs > 0, "must have at least one run");
+    Simulation { runs: (0..options.runs).map(|_| run(model, options, rng)).collect() }
+}
+
+fn run(model: &Model, options: &Options, rng: &mut impl Rng) -> Run {
+    let think_rate = 1.0 / options.think_time.as_secs_f64();
+    let service_rates: Vec<f64> =
+        (0..=options.clients).map(|n| model.throughput_at_concurrency(n)).collect();
+
+    // With no think time, every client has a request outstanding at all times.
+    let mut queue: VecDeque<f64> = VecDeque::new();
+    if think_rate.is_infinite() {
+        queue.extend((0..options.clients).map(|_| 0.0));
+    }
+
+    let (mut now, mut start) = (0.0, 0.0);
+    let (mut completed, mut total_latency, mut area) = (0, 0.0, 0.0);
+    while completed < options.warm_up + options.requests {
+        let n = queue.len();
+        let arrival_rate = f64::from(options.clients - n as u32) * think_rate;
+        let arrival_rate = if arrival_rate.is_nan() { 0.0 } else { arrival_rate };
+        let total_rate = arrival_rate + service_rates[n];
+        let dt = exponential(rng, total_rate);
+        now += dt;
+        if completed >= options.warm_up {
+            area += n as f64 * dt;
+        }
+
+        if rng.gen::<f64>() * total_rate < arrival_rate {
+            queue.push_back(now);
+        } else {
+            let arrived = queue.pop_front().expect("no requests in the system");
+            completed += 1;
+            if completed == options.warm_up {
+                start = now;
+            } else if completed > options.warm_up {
+                total_latency += now - arrived;
+            }
+            if think_rate.is_infinite() {
+                queue.push_back(now);
+            }
+        }
+    }
+
+    let elapsed = now - start;
+    Run {
+        throughput: options.requests as f64 / elapsed,
+        latency: total_latency / options.requests as f64,
+        concurrency: area / elapsed,
+    }
+}
+
+/// Sample from the exponential distribution with the given rate.
+fn exponential(rng: &mut impl Rng, rate: f64) -> f64 {
+    -(1.0 - rng.gen::<f64>()).ln() / rate
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn simulate_without_think_time() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        let options = Options { clients: 16, ..Default::default() };
+        let mut rng = StdRng::seed_from_u64(0xDEADBEEF);
+        let sim = simulate_with_rng(&model, &options, &mut rng);
+
+        assert_eq!(sim.runs.len(), 20);
+        for r in &sim.runs {
+            assert_relative_eq!(r.concurrency, 16.0, max_relative = 0.001);
+        }
+        let x = sim.throughput();
+        assert_relative_eq!(x.mean, model.throughput_at_concurrency(16), max_relative = 0.01);
+        assert!(x.std_dev > 0.0 && x.min < x.mean && x.mean < x.max);
+        assert_relative_eq!(
+            sim.latency().mean,
+            model.latency_at_concurrency(16),
+            max_relative = 0.01
+        );
+    }
+
+    #[test]
+    fn simulate_with_think_time() {
+        let model: Model = MEASUREMENTS.iter().collect();
+        let think_time = Duration::from_millis(10);
+        let options = Options { clients: 64, think_time, ..Default::default() };
+        let mut rng = StdRng::seed_from_u64(0xDEADBEEF);
+        let sim = simulate_with_rng(&model, &options, &mut rng);
+
+        for r in &sim.runs {
+            // Little's law applies to the whole closed loop.
+            assert_relative_eq!(
+                r.throughput * (r.latency + think_time.as_secs_f64()),
+                64.0,
+                max_relative = 0.05
+            );
+            assert!(r.concurrency < 64.0);
+            assert!(r.throughput < model.throughput_at_concurrency(64));
+        }
+    }
+}
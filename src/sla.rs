@@ -0,0 +1,146 @@
+use std::time::Duration;
+
+use crate::Model;
+
+/// A service level agreement which a system must meet, via [Model::satisfies].
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sla {
+    /// The minimum throughput, in events/sec.
+    pub min_throughput: f64,
+    /// The maximum mean latency.
+    pub max_latency: Duration,
+    /// The concurrency at which the system will be run. If `None`, the system may be run at
+    /// whichever concurrency gives the highest throughput within the maximum latency.
+    pub at_concurrency: Option<u32>,
+}
+
+/// One of the constraints of an [Sla].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Constraint {
+    /// The minimum throughput.
+    Throughput,
+    /// The maximum latency.
+    Latency,
+}
+
+/// Whether, and by what margin, a system can meet an [Sla], via [Model::satisfies].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlaVerdict {
+    /// Whether the system meets both constraints.
+    pub satisfied: bool,
+    /// The concurrency at which the system was evaluated.
+    pub concurrency: u32,
+    /// The expected throughput at that concurrency, in events/sec.
+    pub throughput: f64,
+    /// The expected mean latency at that concurrency, in seconds.
+    pub latency: f64,
+    /// The relative margin by which the throughput exceeds the minimum. Negative if it falls
+    /// short.
+    pub throughput_margin: f64,
+    /// The relative margin by which the latency is below the maximum. Negative if it exceeds it.
+    pub latency_margin: f64,
+    /// The constraint with the smaller margin, which limits the system.
+    pub binding: Constraint,
+}
+
+impl Model {
+    /// Determine whether the modeled system can meet the given service level agreement, and by
+    /// what margin.
+    ///
+    /// If the agreement doesn't fix a concurrency, the system is evaluated at the concurrency with
+    /// the highest throughput whose latency is within the agreement, or at a single concurrent
+    /// event if there is none.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use usl::{Model, Sla};
+    ///
+    /// let model = Model { sigma: 0.02, kappa: 0.0001, lambda: 1000.0 };
+    /// let sla = Sla {
+    ///     min_throughput: 20_000.0,
+    ///     max_latency: Duration::from_millis(5),
+    ///     at_concurrency: None,
+    /// };
+    /// let verdict = model.satisfies(&sla);
+    /// println!("{} ({:?}-bound)", verdict.satisfied, verdict.binding);
+    /// ```
+    #[must_use]
+    pub fn satisfies(&self, sla: &Sla) -> SlaVerdict {
+        let max_latency = sla.max_latency.as_secs_f64();
+        let n = sla.at_concurrency.unwrap_or_else(|| {
+            let n = self.max_concurrency_within_latency(sla.max_latency).map_or(1, |(n, _)| n);
+            self.integral_peak().map_or(n, |peak| n.min(peak))
+        });
+        let throughput = self.throughput_at_concurrency(n);
+        let latency = self.latency_at_concurrency(n);
+        let throughput_margin = (throughput - sla.min_throughput) / sla.min_throughput;
+        let latency_margin = (max_latency - latency) / max_latency;
+        SlaVerdict {
+            satisfied: throughput_margin >= 0.0 && latency_margin >= 0.0,
+            concurrency: n,
+            throughput,
+            latency,
+            throughput_margin,
+            latency_margin,
+            binding: if throughput_margin <= latency_margin {
+                Constraint::Throughput
+            } else {
+                Constraint::Latency
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn satisfies() {
+        let model: Model = MEASUREMENTS.iter().collect();
+
+        let sla = Sla {
+            min_throughput: 10_000.0,
+            max_latency: Duration::from_millis(5),
+            at_concurrency: Some(20),
+        };
+        let verdict = model.satisfies(&sla);
+        assert!(verdict.satisfied);
+        assert_eq!(verdict.concurrency, 20);
+        assert_relative_eq!(verdict.throughput, model.throughput_at_concurrency(20));
+        assert_relative_eq!(verdict.throughput_margin, verdict.throughput / 10_000.0 - 1.0);
+        assert_eq!(verdict.binding, Constraint::Throughput);
+
+        // Unconstrained by latency, the system runs at its peak but can't reach the throughput.
+        let verdict = model.satisfies(&Sla {
+            min_throughput: 13_000.0,
+            max_latency: Duration::from_secs(1),
+            at_concurrency: None,
+        });
+        assert!(!verdict.satisfied);
+        assert_eq!(verdict.concurrency, 36);
+        assert!(verdict.throughput_margin < 0.0);
+        assert_eq!(verdict.binding, Constraint::Throughput);
+
+        // A tight latency limit caps the concurrency, and so the throughput.
+        let verdict = model.satisfies(&Sla {
+            min_throughput: 12_000.0,
+            max_latency: Duration::from_micros(1500),
+            at_concurrency: None,
+        });
+        assert!(!verdict.satisfied);
+        assert_eq!(verdict.concurrency, 14);
+        assert!(verdict.latency_margin >= 0.0 && verdict.throughput_margin < 0.0);
+
+        let verdict = model.satisfies(&Sla { at_concurrency: Some(30), ..sla });
+        assert!(verdict.satisfied);
+        assert!(verdict.latency > model.latency_at_concurrency(20));
+        assert_relative_eq!(verdict.latency_margin, 1.0 - verdict.latency / 0.005);
+    }
+}
@@ -0,0 +1,144 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::Dataset;
+
+/// A way of dividing a [Dataset] into training and test sets, via [Dataset::split].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SplitStrategy {
+    /// Assign measurements to the test set at random.
+    Random,
+    /// Sort the measurements by concurrency, divide them into as many equally sized strata as
+    /// there are test measurements, and assign one measurement from each stratum to the test set
+    /// at random. The test set spans the full range of concurrency, even when each concurrency
+    /// level has been measured only once.
+    Stratified,
+    /// Assign the measurements at the highest concurrency levels to the test set, keeping each
+    /// level's measurements together. This measures how well a model extrapolates beyond the
+    /// measured concurrency levels, which is how models are usually used for capacity planning.
+    HoldOutHighest,
+}
+
+impl Dataset {
+    /// Divide the dataset into a training set containing roughly `ratio` of its measurements and a
+    /// test set containing the rest, both in their original order.
+    ///
+    /// [SplitStrategy::HoldOutHighest] holds out whole concurrency levels, so its training set may
+    /// be smaller than requested.
+    ///
+    /// ```
+    /// use usl::{Dataset, Model, SplitStrategy};
+    ///
+    /// let dataset: Dataset = [
+    ///     (1, 65.0), (18, 996.0), (36, 1652.0), (72, 1853.0), (108, 1829.0), (144, 1775.0),
+    ///     (180, 1720.0), (216, 1680.0),
+    /// ]
+    /// .iter()
+    /// .map(|&m| m.into())
+    /// .collect();
+    /// let (training, test) = dataset.split(0.75, SplitStrategy::HoldOutHighest);
+    /// assert_eq!(test.iter().map(|m| m.n).collect::<Vec<f64>>(), vec![180.0, 216.0]);
+    ///
+    /// let model = Model::build(&training);
+    /// let report = model.evaluate(&test);
+    /// println!("extrapolation MAPE: {}", report.mape);
+    /// ```
+    ///
+    /// Panics if `ratio` is not between 0 and 1.
+    #[must_use]
+    pub fn split(&self, ratio: f64, strategy: SplitStrategy) -> (Dataset, Dataset) {
+        self.split_with_rng(ratio, strategy, &mut rand::thread_rng())
+    }
+
+    /// Like [Dataset::split], but using the given random number generator.
+    ///
+    /// Passing a seeded generator, e.g. `StdRng::seed_from_u64`, makes the results reproducible.
+    #[must_use]
+    pub fn split_with_rng(
+        &self,
+        ratio: f64,
+        strategy: SplitStrategy,
+        rng: &mut impl Rng,
+    ) -> (Dataset, Dataset) {
+        assert!((0.0..=1.0).contains(&ratio), "ratio must be between 0 and 1");
+        let n_test = self.len() - (self.len() as f64 * ratio).round() as usize;
+
+        let mut test = vec![false; self.len()];
+        let mut by_concurrency: Vec<usize> = (0..self.len()).collect();
+        by_concurrency.sort_by(|&a, &b| self[a].n.total_cmp(&self[b].n));
+        match strategy {
+            SplitStrategy::Random => {
+                for &i in by_concurrency.choose_multiple(rng, n_test) {
+                    test[i] = true;
+                }
+            }
+            SplitStrategy::Stratified => {
+                for s in 0..n_test {
+                    let stratum =
+                        &by_concurrency[s * self.len() / n_test..(s + 1) * self.len() / n_test];
+                    test[*stratum.choose(rng).expect("strata are never empty")] = true;
+                }
+            }
+            SplitStrategy::HoldOutHighest => {
+                for (held, (k, &i)) in by_concurrency.iter().enumerate().rev().enumerate() {
+                    let boundary =
+                        k + 1 == self.len() || self[by_concurrency[k + 1]].n != self[i].n;
+                    if held >= n_test && boundary {
+                        break;
+                    }
+                    test[i] = true;
+                }
+            }
+        }
+
+        let mut training = Dataset::default().with_metadata(self.metadata().clone());
+        let mut testing = Dataset::default().with_metadata(self.metadata().clone());
+        for (&m, test) in self.iter().zip(test) {
+            if test {
+                testing.push(m);
+            } else {
+                training.push(m);
+            }
+        }
+        (training, testing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+    use crate::Measurement;
+
+    #[test]
+    fn split() {
+        let dataset: Dataset = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let mut rng = StdRng::seed_from_u64(0xDEADBEEF);
+        let concurrency = |d: &Dataset| d.iter().map(|m| m.n).collect::<Vec<f64>>();
+
+        let (training, test) = dataset.split_with_rng(0.75, SplitStrategy::Random, &mut rng);
+        assert_eq!((training.len(), test.len()), (24, 8));
+        assert!(concurrency(&test).windows(2).all(|w| w[0] < w[1]));
+
+        let (training, test) = dataset.split_with_rng(0.75, SplitStrategy::Stratified, &mut rng);
+        assert_eq!((training.len(), test.len()), (24, 8));
+        for (s, n) in concurrency(&test).into_iter().enumerate() {
+            assert!(n > (s * 4) as f64 && n <= ((s + 1) * 4) as f64, "{} in stratum {}", n, s);
+        }
+
+        let mut replicated = dataset.clone();
+        replicated.push(Measurement::concurrency_and_throughput(29, 12_100.0));
+        // Four measurements are requested, but both measurements at 29 are held out together.
+        let (training, test) =
+            replicated.split_with_rng(0.88, SplitStrategy::HoldOutHighest, &mut rng);
+        assert_eq!(concurrency(&test), vec![29.0, 30.0, 31.0, 32.0, 29.0]);
+        assert_eq!(training.len(), 28);
+
+        let (training, test) = dataset.split_with_rng(1.0, SplitStrategy::Stratified, &mut rng);
+        assert_eq!((training.len(), test.len()), (32, 0));
+    }
+}
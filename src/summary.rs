@@ -0,0 +1,96 @@
+use std::fmt;
+
+use crate::{FitQuality, Measurement, Model};
+
+/// The marginal throughput, as a fraction of λ, below which [ModelSummary::knee] is found.
+const KNEE_THRESHOLD: f64 = 0.5;
+
+/// Which effect limits a system's scalability, via [Model::summary].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Classification {
+    /// The system is constrained by contention effects; see [Model::is_contention_constrained].
+    ContentionConstrained,
+    /// The system is constrained by coherency effects; see [Model::is_coherency_constrained].
+    CoherencyConstrained,
+    /// The system is linearly scalable; see [Model::is_limitless].
+    LinearlyScalable,
+}
+
+impl fmt::Display for Classification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Classification::ContentionConstrained => "contention constrained",
+            Classification::CoherencyConstrained => "coherency constrained",
+            Classification::LinearlyScalable => "linearly scalable",
+        })
+    }
+}
+
+/// A summary of a model and how well it fits its measurements, for reporting, via
+/// [Model::summary].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModelSummary {
+    /// The model.
+    pub model: Model,
+    /// The model's [Model::max_concurrency], or `None` if it has no finite peak.
+    pub max_concurrency: Option<u32>,
+    /// The model's [Model::max_throughput].
+    pub max_throughput: f64,
+    /// The model's [Model::knee], where the marginal throughput drops below half of λ.
+    pub knee: Option<u32>,
+    /// The model's goodness of fit to the measurements.
+    pub quality: FitQuality,
+    /// Which effect limits the system's scalability, or `None` if contention and coherency are
+    /// equally limiting.
+    pub classification: Option<Classification>,
+}
+
+impl Model {
+    /// Summarize the model's parameters, derived quantities, and goodness of fit to the given
+    /// measurements.
+    #[must_use]
+    pub fn summary(&self, measurements: &[Measurement]) -> ModelSummary {
+        let classification = if self.is_contention_constrained() {
+            Some(Classification::ContentionConstrained)
+        } else if self.is_coherency_constrained() {
+            Some(Classification::CoherencyConstrained)
+        } else if self.is_limitless() {
+            Some(Classification::LinearlyScalable)
+        } else {
+            None
+        };
+        ModelSummary {
+            model: *self,
+            max_concurrency: self.checked_max_concurrency(),
+            max_throughput: self.max_throughput(),
+            knee: self.knee(KNEE_THRESHOLD),
+            quality: self.fit_quality(measurements),
+            classification,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn summary() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let model = Model::build(&measurements);
+        let summary = model.summary(&measurements);
+
+        assert_eq!(summary.max_concurrency, Some(35));
+        assert_relative_eq!(summary.max_throughput, model.max_throughput());
+        assert_eq!(summary.knee, model.knee(0.5));
+        assert!(summary.knee.unwrap() < 35);
+        assert_relative_eq!(summary.quality.rmse, model.fit_quality(&measurements).rmse);
+        assert_eq!(summary.classification, Some(Classification::ContentionConstrained));
+        assert_eq!(Classification::ContentionConstrained.to_string(), "contention constrained");
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use crate::{median, robust_scale, Dataset, Measurement};
+
+/// A rule for deciding which measurements are outliers among their concurrency-level peers, via
+/// [Dataset::trim_outliers].
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TrimPolicy {
+    /// Remove measurements whose throughput is more than the given number of standard deviations
+    /// from the mean throughput at their concurrency level. A single large outlier inflates the
+    /// standard deviation, so this can fail to detect outliers at levels with few measurements.
+    ZScore(f64),
+    /// Remove measurements whose throughput is more than the given number of robust standard
+    /// deviations, estimated via the median absolute deviation, from the median throughput at
+    /// their concurrency level. This is insensitive to the outliers themselves, and a threshold
+    /// of 3 is a common choice.
+    Mad(f64),
+}
+
+/// A measurement removed by [Dataset::trim_outliers].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrimmedPoint {
+    /// The index of the measurement in the original dataset.
+    pub index: usize,
+    /// The measurement.
+    pub measurement: Measurement,
+    /// The measurement's standardized deviation from its peers, per the [TrimPolicy].
+    pub score: f64,
+}
+
+/// The result of removing outliers from a [Dataset], via [Dataset::trim_outliers].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trimmed {
+    /// The remaining measurements, in their original order.
+    pub dataset: Dataset,
+    /// The removed measurements, in their original order.
+    pub removed: Vec<TrimmedPoint>,
+}
+
+impl Dataset {
+    /// Remove the measurements whose throughput deviates from that of the other measurements at
+    /// the same concurrency level by more than the policy allows.
+    ///
+    /// Measurements are only compared with their peers, so this finds bad repetitions of a
+    /// benchmark without needing a model of the system. Concurrency levels with fewer than three
+    /// measurements are left untouched. If all but one of a level's measurements are identical,
+    /// [TrimPolicy::Mad] removes the one which differs.
+    ///
+    /// ```
+    /// use usl::{Dataset, TrimPolicy};
+    ///
+    /// let dataset: Dataset =
+    ///     [(1, 100.0), (1, 102.0), (1, 98.0), (1, 101.0), (1, 40.0), (2, 190.0), (2, 195.0)]
+    ///         .iter()
+    ///         .map(|&m| m.into())
+    ///         .collect();
+    /// let trimmed = dataset.trim_outliers(TrimPolicy::Mad(3.0));
+    /// assert_eq!(trimmed.dataset.len(), 6);
+    /// assert_eq!(trimmed.removed[0].index, 4);
+    /// ```
+    #[must_use]
+    pub fn trim_outliers(&self, policy: TrimPolicy) -> Trimmed {
+        let mut levels: HashMap<u64, Vec<usize>> = HashMap::new();
+        for (i, m) in self.iter().enumerate() {
+            levels.entry(m.n.to_bits()).or_default().push(i);
+        }
+
+        let mut scores = vec![0.0; self.len()];
+        for indexes in levels.values().filter(|indexes| indexes.len() >= 3) {
+            let mut x: Vec<f64> = indexes.iter().map(|&i| self[i].x).collect();
+            let (center, scale) = match policy {
+                TrimPolicy::ZScore(_) => {
+                    let n = x.len() as f64;
+                    let mean = x.iter().sum::<f64>() / n;
+                    let var = x.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+                    (mean, var.sqrt())
+                }
+                TrimPolicy::Mad(_) => {
+                    x.sort_by(f64::total_cmp);
+                    let median = median(&x);
+                    let deviations: Vec<f64> = x.iter().map(|x| x - median).collect();
+                    (median, robust_scale(&deviations))
+                }
+            };
+            for &i in indexes {
+                let deviation = self[i].x - center;
+                // Identical peers have no spread; only measurements which differ from them are
+                // outliers.
+                scores[i] = if deviation == 0.0 { 0.0 } else { deviation / scale };
+            }
+        }
+
+        let threshold = match policy {
+            TrimPolicy::ZScore(t) | TrimPolicy::Mad(t) => t,
+        };
+        let mut dataset = Dataset::default().with_metadata(self.metadata().clone());
+        let mut removed = Vec::new();
+        for (index, (&measurement, score)) in self.iter().zip(scores).enumerate() {
+            if score.abs() > threshold {
+                removed.push(TrimmedPoint { index, measurement, score });
+            } else {
+                dataset.push(measurement);
+            }
+        }
+        Trimmed { dataset, removed }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn trim_outliers() {
+        let mut dataset: Dataset = MEASUREMENTS
+            .iter()
+            .flat_map(|&(n, x)| [(n, x * 0.99), (n, x), (n, x * 1.01), (n, x * 1.02)])
+            .map(Measurement::from)
+            .collect();
+        dataset.push(Measurement::concurrency_and_throughput(10, 3000.0));
+
+        let trimmed = dataset.trim_outliers(TrimPolicy::Mad(3.0));
+        assert_eq!(trimmed.dataset.len(), 128);
+        assert_eq!(trimmed.removed.len(), 1);
+        assert_eq!(trimmed.removed[0].index, 128);
+        assert_eq!(trimmed.removed[0].measurement.x, 3000.0);
+        assert!(trimmed.removed[0].score < -3.0);
+
+        // The outlier inflates the standard deviation of its five peers enough to mask itself.
+        let trimmed = dataset.trim_outliers(TrimPolicy::ZScore(3.0));
+        assert_eq!(trimmed.dataset.len(), 129);
+        let trimmed = dataset.trim_outliers(TrimPolicy::ZScore(1.5));
+        assert_eq!(trimmed.removed.len(), 1);
+        assert_relative_eq!(trimmed.removed[0].score, -1.7888, max_relative = 0.001);
+
+        let identical: Dataset =
+            [(1, 10.0), (1, 10.0), (1, 10.0), (1, 11.0)].iter().map(|&m| m.into()).collect();
+        let trimmed = identical.trim_outliers(TrimPolicy::Mad(3.0));
+        assert_eq!(trimmed.removed.len(), 1);
+        assert_eq!(trimmed.removed[0].index, 3);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::Measurement;
+
+/// The unit in which a rate (e.g. throughput) is expressed.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RateUnit {
+    /// Events per second. This is the unit used by [Measurement] and [crate::Model].
+    #[default]
+    PerSecond,
+    /// Events per minute.
+    PerMinute,
+    /// Events per hour.
+    PerHour,
+}
+
+impl RateUnit {
+    const fn seconds(self) -> f64 {
+        match self {
+            RateUnit::PerSecond => 1.0,
+            RateUnit::PerMinute => 60.0,
+            RateUnit::PerHour => 3_600.0,
+        }
+    }
+
+    /// Convert a rate in this unit to events per second.
+    #[must_use]
+    pub fn to_per_second(self, v: f64) -> f64 {
+        v / self.seconds()
+    }
+
+    /// Convert a rate in events per second to this unit.
+    #[must_use]
+    pub fn from_per_second(self, v: f64) -> f64 {
+        v * self.seconds()
+    }
+}
+
+/// The unit in which a latency is expressed.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LatencyUnit {
+    /// Seconds. This is the unit used by [Measurement] and [crate::Model].
+    #[default]
+    Seconds,
+    /// Milliseconds.
+    Milliseconds,
+    /// Microseconds.
+    Microseconds,
+    /// Nanoseconds.
+    Nanoseconds,
+}
+
+impl LatencyUnit {
+    const fn seconds(self) -> f64 {
+        match self {
+            LatencyUnit::Seconds => 1.0,
+            LatencyUnit::Milliseconds => 1e-3,
+            LatencyUnit::Microseconds => 1e-6,
+            LatencyUnit::Nanoseconds => 1e-9,
+        }
+    }
+
+    /// Convert a latency in this unit to seconds.
+    #[must_use]
+    pub fn to_seconds(self, v: f64) -> f64 {
+        v * self.seconds()
+    }
+
+    /// Convert a latency in seconds to this unit.
+    #[must_use]
+    pub fn from_seconds(self, v: f64) -> f64 {
+        v / self.seconds()
+    }
+}
+
+impl Measurement {
+    /// Create a measurement of a system's throughput, in events per minute, at a given level of
+    /// concurrency. The latency of the system is derived via Little's Law.
+    ///
+    /// ```
+    /// use usl::Measurement;
+    ///
+    /// let m = Measurement::concurrency_and_throughput_per_minute(10, 60_000.0);
+    /// assert_eq!(m.x, 1_000.0);
+    /// ```
+    #[must_use]
+    pub fn concurrency_and_throughput_per_minute(n: u32, x: f64) -> Measurement {
+        Measurement::concurrency_and_throughput_in(n, x, RateUnit::PerMinute)
+    }
+
+    /// Create a measurement of a system's throughput, in the given unit, at a given level of
+    /// concurrency. The latency of the system is derived via Little's Law.
+    #[must_use]
+    pub fn concurrency_and_throughput_in(n: u32, x: f64, unit: RateUnit) -> Measurement {
+        Measurement::concurrency_and_throughput(n, unit.to_per_second(x))
+    }
+
+    /// Create a measurement of a system's latency, in the given unit, at a given level of
+    /// concurrency. The throughput of the system is derived via Little's Law.
+    ///
+    /// ```
+    /// use usl::{LatencyUnit, Measurement};
+    ///
+    /// let m = Measurement::concurrency_and_latency_in(10, 20.0, LatencyUnit::Milliseconds);
+    /// assert_eq!(m.r, 0.02);
+    /// ```
+    #[must_use]
+    pub fn concurrency_and_latency_in(n: u32, r: f64, unit: LatencyUnit) -> Measurement {
+        let n = n.into();
+        let r = unit.to_seconds(r);
+        Measurement { n, x: n / r, r }
+    }
+
+    /// Create a measurement of a system's latency at a given level of throughput, each in the
+    /// given unit. The concurrency of the system is derived via Little's Law.
+    #[must_use]
+    pub fn throughput_and_latency_in(
+        x: f64,
+        rate_unit: RateUnit,
+        r: f64,
+        latency_unit: LatencyUnit,
+    ) -> Measurement {
+        let x = rate_unit.to_per_second(x);
+        let r = latency_unit.to_seconds(r);
+        Measurement { n: x * r, x, r }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn unit_conversions() {
+        let m = Measurement::concurrency_and_throughput_in(30, 3_600.0, RateUnit::PerHour);
+        assert_relative_eq!(m.x, 1.0);
+        assert_relative_eq!(m.r, 30.0);
+
+        let m = Measurement::concurrency_and_latency_in(10, 500.0, LatencyUnit::Microseconds);
+        assert_relative_eq!(m.r, 0.0005);
+        assert_relative_eq!(m.x, 20_000.0);
+
+        let m = Measurement::throughput_and_latency_in(
+            1_200.0,
+            RateUnit::PerMinute,
+            250.0,
+            LatencyUnit::Milliseconds,
+        );
+        assert_relative_eq!(m.x, 20.0);
+        assert_relative_eq!(m.r, 0.25);
+        assert_relative_eq!(m.n, 5.0);
+
+        assert_relative_eq!(RateUnit::PerMinute.from_per_second(2.0), 120.0);
+        assert_relative_eq!(LatencyUnit::Nanoseconds.from_seconds(0.5), 5e8);
+    }
+}
@@ -0,0 +1,208 @@
+//! Functions for validating models and the measurements they're built from.
+
+use crate::{Measurement, Model, ModelFitter, MIN_MEASUREMENTS};
+
+/// The out-of-sample prediction errors of a cross-validated model, via [k_fold].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrossValidation {
+    /// The error (observed minus predicted throughput) of each measurement when predicted by a
+    /// model fitted without it, in the same order as the measurements.
+    pub errors: Vec<f64>,
+    /// The root mean squared error of each fold.
+    pub folds: Vec<f64>,
+    /// The overall root mean squared error.
+    pub rmse: f64,
+    /// The overall mean absolute error.
+    pub mae: f64,
+}
+
+impl CrossValidation {
+    /// The index of the measurement with the largest absolute out-of-sample error.
+    #[must_use]
+    pub fn worst(&self) -> usize {
+        (0..self.errors.len())
+            .max_by(|&a, &b| self.errors[a].abs().total_cmp(&self.errors[b].abs()))
+            .expect("no errors")
+    }
+}
+
+/// Cross-validate a model of the given measurements by splitting them into `k` folds, fitting a
+/// model to each set of `k - 1` folds, and measuring its error on the held-out fold.
+///
+/// Measurements are assigned to folds by index, round-robin, so measurements sorted by concurrency
+/// produce folds which each span the full range of concurrency levels. An out-of-sample error much
+/// larger than the in-sample error (see [Model::fit_quality]) indicates the model is overfitted.
+///
+/// Panics if `k` is less than 2 or greater than the number of measurements, or if the largest fold
+/// leaves fewer than [MIN_MEASUREMENTS] measurements to fit.
+#[must_use]
+pub fn k_fold(measurements: &[Measurement], k: usize) -> CrossValidation {
+    assert!(k >= 2, "must have at least 2 folds");
+    assert!(k <= measurements.len(), "must have at least as many measurements as folds");
+    assert!(
+        measurements.len() - measurements.len().div_ceil(k) >= MIN_MEASUREMENTS,
+        "must have at least {} measurements outside each fold",
+        MIN_MEASUREMENTS
+    );
+
+    let mut errors = vec![0.0; measurements.len()];
+    let mut folds = Vec::with_capacity(k);
+    for fold in 0..k {
+        let training: Vec<Measurement> = measurements
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| i % k != fold)
+            .map(|(_, &m)| m)
+            .collect();
+        let model = match Model::try_fit(&ModelFitter::new(&training), None) {
+            Ok(model) => model,
+            Err(err) => panic!("{}", err),
+        };
+
+        let mut sse = 0.0;
+        let mut count = 0;
+        for (i, m) in measurements.iter().enumerate().skip(fold).step_by(k) {
+            errors[i] = m.x - model.throughput_at_concurrency_f64(m.n);
+            sse += errors[i] * errors[i];
+            count += 1;
+        }
+        folds.push((sse / count as f64).sqrt());
+    }
+
+    let n = errors.len() as f64;
+    CrossValidation {
+        rmse: (errors.iter().map(|e| e * e).sum::<f64>() / n).sqrt(),
+        mae: errors.iter().map(|e| e.abs()).sum::<f64>() / n,
+        errors,
+        folds,
+    }
+}
+
+/// Cross-validate a model of the given measurements by fitting a model to each subset with one
+/// measurement left out, and measuring its error on that measurement.
+///
+/// Equivalent to [k_fold] with one fold per measurement, but each fit is started from the
+/// parameters of a model fitted to all the measurements, which converges much faster. This makes
+/// it practical for the small datasets typical of load tests, where [CrossValidation::worst]
+/// identifies the single measurement the fit depends on most.
+#[must_use]
+pub fn leave_one_out(measurements: &[Measurement]) -> CrossValidation {
+    let full = Model::build(measurements);
+    let errors: Vec<f64> = (0..measurements.len())
+        .map(|i| {
+            let mut training = measurements.to_vec();
+            let m = training.remove(i);
+            let fitter = ModelFitter { start: Some(full), ..ModelFitter::new(&training) };
+            let model = match Model::try_fit(&fitter, None) {
+                Ok(model) => model,
+                Err(err) => panic!("{}", err),
+            };
+            m.x - model.throughput_at_concurrency_f64(m.n)
+        })
+        .collect();
+
+    let n = errors.len() as f64;
+    CrossValidation {
+        rmse: (errors.iter().map(|e| e * e).sum::<f64>() / n).sqrt(),
+        mae: errors.iter().map(|e| e.abs()).sum::<f64>() / n,
+        folds: errors.iter().map(|e| e.abs()).collect(),
+        errors,
+    }
+}
+
+/// A measurement whose concurrency, throughput, and latency disagree, via [littles_law].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Inconsistency {
+    /// The index of the measurement.
+    pub index: usize,
+    /// The concurrency implied by the measurement's throughput and latency, `X·R`.
+    pub implied_concurrency: f64,
+    /// The relative difference between the measured and implied concurrency,
+    /// `(N - X·R)/N`.
+    pub relative_error: f64,
+}
+
+/// Find the measurements whose independently measured concurrency, throughput, and latency
+/// disagree with Little's Law, `N = X·R`, by more than the given relative tolerance (e.g. `0.05`
+/// for 5%).
+///
+/// Measurements created via [Measurement]'s constructors derive one quantity from the other two
+/// and are always consistent. When all three are measured separately, e.g. from APM data, an
+/// inconsistency indicates a measurement bug (such as mismatched sampling windows or dropped
+/// events) which would silently corrupt a fit.
+#[must_use]
+pub fn littles_law(measurements: &[Measurement], tolerance: f64) -> Vec<Inconsistency> {
+    measurements
+        .iter()
+        .enumerate()
+        .filter_map(|(index, m)| {
+            let implied_concurrency = m.x * m.r;
+            let relative_error = (m.n - implied_concurrency) / m.n;
+            (relative_error.abs() > tolerance || relative_error.is_nan()).then_some(Inconsistency {
+                index,
+                implied_concurrency,
+                relative_error,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn k_fold_cross_validation() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let cv = k_fold(&measurements, 4);
+
+        assert_eq!(cv.errors.len(), 32);
+        assert_eq!(cv.folds.len(), 4);
+        assert!(cv.rmse >= Model::build(&measurements).fit_quality(&measurements).rmse);
+        assert!(cv.mae > 0.0 && cv.mae <= cv.rmse);
+    }
+
+    #[test]
+    #[should_panic(expected = "must have at least 6 measurements outside each fold")]
+    fn k_fold_too_few_measurements() {
+        let measurements: Vec<Measurement> = MEASUREMENTS[..6].iter().map(|&m| m.into()).collect();
+        let _ = k_fold(&measurements, 3);
+    }
+
+    #[test]
+    fn leave_one_out_cross_validation() {
+        let mut measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let cv = leave_one_out(&measurements);
+        assert_eq!(cv.errors.len(), 32);
+        assert_eq!(cv.folds.len(), 32);
+
+        let k_fold = k_fold(&measurements, measurements.len());
+        for (a, b) in cv.errors.iter().zip(k_fold.errors.iter()) {
+            assert_relative_eq!(a, b, max_relative = 0.001);
+        }
+
+        measurements[10] = Measurement::concurrency_and_throughput(11, 4000.0);
+        assert_eq!(leave_one_out(&measurements).worst(), 10);
+    }
+
+    #[test]
+    fn littles_law_consistency() {
+        let mut measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        assert!(littles_law(&measurements, 1e-9).is_empty());
+
+        measurements[3].r *= 1.1;
+        measurements[7].x *= 0.97;
+        let inconsistencies = littles_law(&measurements, 0.05);
+        assert_eq!(inconsistencies.len(), 1);
+        assert_eq!(inconsistencies[0].index, 3);
+        assert_relative_eq!(inconsistencies[0].relative_error, -0.1, max_relative = 1e-9);
+        assert_relative_eq!(inconsistencies[0].implied_concurrency, measurements[3].n * 1.1);
+
+        assert_eq!(littles_law(&measurements, 0.01).len(), 2);
+    }
+}
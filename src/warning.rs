@@ -0,0 +1,221 @@
+use std::fmt;
+
+use crate::{Measurement, Model};
+
+/// A caveat about a fitted model, returned by [Model::warnings] and alongside models built via
+/// [ModelBuilder::build_with_warnings](crate::ModelBuilder::build_with_warnings).
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FitWarning {
+    /// The model was fitted to fewer than [MIN_MEASUREMENTS](crate::MIN_MEASUREMENTS)
+    /// measurements, leaving few degrees of freedom. Its parameters are poorly constrained and
+    /// should be treated as exploratory.
+    Underdetermined {
+        /// The number of measurements the model was fitted to.
+        measurements: usize,
+    },
+    /// The coefficient of contention is negative, which implies superlinear scaling.
+    NegativeSigma {
+        /// The fitted value of σ.
+        sigma: f64,
+    },
+    /// The coefficient of crosstalk is negative, which implies superlinear scaling.
+    NegativeKappa {
+        /// The fitted value of κ.
+        kappa: f64,
+    },
+    /// The coefficient of performance is lower than the throughput observed at a concurrency of
+    /// one, which the model should predict.
+    LambdaBelowObserved {
+        /// The fitted value of λ.
+        lambda: f64,
+        /// The highest throughput observed at a concurrency of one or less, per unit of concurrency.
+        observed: f64,
+    },
+    /// The concurrency at which throughput peaks is far outside the measured range of concurrency,
+    /// so predictions about the peak are extrapolations.
+    PeakOutsideRange {
+        /// The concurrency at which throughput peaks.
+        peak: f64,
+        /// The lowest and highest measured concurrency.
+        measured: (f64, f64),
+    },
+    /// The concurrency at which throughput peaks is beyond the highest measured concurrency, so
+    /// the peak is an extrapolation rather than an observation. Reported by
+    /// [Model::coverage_warnings] unless the peak is far enough out to be reported as
+    /// [FitWarning::PeakOutsideRange].
+    PeakNotBracketed {
+        /// The concurrency at which throughput peaks.
+        peak: f64,
+        /// The lowest and highest measured concurrency.
+        measured: (f64, f64),
+    },
+    /// The measurements cover too few distinct concurrency levels to constrain the shape of the
+    /// throughput curve. Reported by [Model::coverage_warnings].
+    TooFewLevels {
+        /// The number of distinct concurrency levels measured.
+        levels: usize,
+        /// The minimum number of distinct concurrency levels required.
+        required: usize,
+    },
+}
+
+/// How far beyond the highest measured concurrency the throughput peak can be before it is
+/// reported, as a multiple of that concurrency.
+const PEAK_RANGE_FACTOR: f64 = 10.0;
+
+impl Model {
+    /// Check the model, fitted to the given measurements, for non-physical or suspicious
+    /// parameters.
+    #[must_use]
+    pub fn warnings(&self, measurements: &[Measurement]) -> Vec<FitWarning> {
+        let mut warnings = Vec::new();
+        if self.sigma < 0.0 {
+            warnings.push(FitWarning::NegativeSigma { sigma: self.sigma });
+        }
+        if self.kappa < 0.0 {
+            warnings.push(FitWarning::NegativeKappa { kappa: self.kappa });
+        }
+
+        let observed = measurements
+            .iter()
+            .filter(|m| m.n <= 1.0)
+            .map(|m| m.x / m.n)
+            .fold(f64::NEG_INFINITY, f64::max);
+        if self.lambda < observed {
+            warnings.push(FitWarning::LambdaBelowObserved { lambda: self.lambda, observed });
+        }
+
+        let measured = measurements
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), m| (lo.min(m.n), hi.max(m.n)));
+        if self.kappa > 0.0 && !measurements.is_empty() {
+            let peak = ((1.0 - self.sigma) / self.kappa).sqrt();
+            if peak < measured.0 || peak > measured.1 * PEAK_RANGE_FACTOR {
+                warnings.push(FitWarning::PeakOutsideRange { peak, measured });
+            }
+        }
+        warnings
+    }
+
+    /// Check whether the given measurements, to which the model was fitted, cover enough of the
+    /// system's concurrency levels for its peak to be meaningful.
+    ///
+    /// Warns if the measurements have fewer than `min_levels` distinct concurrency levels, or if
+    /// the concurrency at which throughput peaks is beyond the highest measured concurrency.
+    #[must_use]
+    pub fn coverage_warnings(
+        &self,
+        measurements: &[Measurement],
+        min_levels: usize,
+    ) -> Vec<FitWarning> {
+        let mut warnings = Vec::new();
+        let mut levels: Vec<f64> = measurements.iter().map(|m| m.n).collect();
+        levels.sort_by(f64::total_cmp);
+        levels.dedup();
+        if levels.len() < min_levels {
+            warnings.push(FitWarning::TooFewLevels { levels: levels.len(), required: min_levels });
+        }
+
+        if let (Some(&lo), Some(&hi)) = (levels.first(), levels.last()) {
+            if self.kappa > 0.0 {
+                let peak = ((1.0 - self.sigma) / self.kappa).sqrt();
+                if peak > hi && peak <= hi * PEAK_RANGE_FACTOR {
+                    warnings.push(FitWarning::PeakNotBracketed { peak, measured: (lo, hi) });
+                }
+            }
+        }
+        warnings
+    }
+}
+
+impl fmt::Display for FitWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FitWarning::Underdetermined { measurements } => {
+                write!(f, "fitted to only {} measurements", measurements)
+            }
+            FitWarning::NegativeSigma { sigma } => write!(f, "σ is negative ({})", sigma),
+            FitWarning::NegativeKappa { kappa } => write!(f, "κ is negative ({})", kappa),
+            FitWarning::LambdaBelowObserved { lambda, observed } => {
+                write!(
+                    f,
+                    "λ ({}) is below observed single-thread throughput ({})",
+                    lambda, observed
+                )
+            }
+            FitWarning::PeakOutsideRange { peak, measured } => write!(
+                f,
+                "peak concurrency ({}) is far outside measured range ({} to {})",
+                peak, measured.0, measured.1
+            ),
+            FitWarning::PeakNotBracketed { peak, measured } => write!(
+                f,
+                "peak concurrency ({}) is beyond measured range ({} to {})",
+                peak, measured.0, measured.1
+            ),
+            FitWarning::TooFewLevels { levels, required } => write!(
+                f,
+                "measured only {} distinct concurrency levels, fewer than {}",
+                levels, required
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn warnings() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+        let model = Model::build(&measurements);
+        assert_eq!(model.warnings(&measurements), vec![]);
+
+        let model = Model { sigma: -0.01, kappa: 1e-7, lambda: 900.0 };
+        assert_eq!(
+            model.warnings(&measurements),
+            vec![
+                FitWarning::NegativeSigma { sigma: -0.01 },
+                FitWarning::LambdaBelowObserved { lambda: 900.0, observed: 955.16 },
+                FitWarning::PeakOutsideRange {
+                    peak: (1.01f64 / 1e-7).sqrt(),
+                    measured: (1.0, 32.0)
+                },
+            ]
+        );
+
+        let model = Model { kappa: -1e-4, ..model };
+        assert_eq!(model.warnings(&measurements)[1], FitWarning::NegativeKappa { kappa: -1e-4 });
+    }
+
+    #[test]
+    fn coverage_warnings() {
+        let measurements: Vec<Measurement> = MEASUREMENTS.iter().map(|&m| m.into()).collect();
+
+        // The test data stops just short of its peak.
+        let model = Model::build(&measurements);
+        assert_eq!(
+            model.coverage_warnings(&measurements, 8),
+            vec![FitWarning::PeakNotBracketed {
+                peak: ((1.0 - model.sigma) / model.kappa).sqrt(),
+                measured: (1.0, 32.0)
+            }]
+        );
+
+        let model = Model { kappa: 2e-3, ..model };
+        assert_eq!(model.coverage_warnings(&measurements, 8), vec![]);
+        assert_eq!(
+            model.coverage_warnings(&measurements[..12], 16),
+            vec![
+                FitWarning::TooFewLevels { levels: 12, required: 16 },
+                FitWarning::PeakNotBracketed {
+                    peak: ((1.0 - model.sigma) / 2e-3).sqrt(),
+                    measured: (1.0, 12.0)
+                },
+            ]
+        );
+    }
+}
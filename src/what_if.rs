@@ -0,0 +1,113 @@
+use crate::Model;
+
+/// A comparison of a model's capacity before and after adjusting its parameters, via
+/// [Model::what_if].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WhatIf {
+    /// The maximum throughput before and after the adjustment.
+    pub max_throughput: (f64, f64),
+    /// The maximum concurrency before and after the adjustment, or `None` if the model has no
+    /// finite peak.
+    pub max_concurrency: (Option<u32>, Option<u32>),
+    /// The relative change in maximum throughput, e.g. `0.2` for a 20% increase.
+    pub throughput_gain: f64,
+}
+
+impl Model {
+    /// Return a copy of the model with the given coefficient of contention, σ.
+    #[must_use]
+    pub const fn with_sigma(self, sigma: f64) -> Model {
+        Model { sigma, ..self }
+    }
+
+    /// Return a copy of the model with the given coefficient of crosstalk, κ.
+    #[must_use]
+    pub const fn with_kappa(self, kappa: f64) -> Model {
+        Model { kappa, ..self }
+    }
+
+    /// Return a copy of the model with the given coefficient of performance, λ.
+    #[must_use]
+    pub const fn with_lambda(self, lambda: f64) -> Model {
+        Model { lambda, ..self }
+    }
+
+    /// Return a copy of the model with σ multiplied by the given factor, e.g. `0.5` to model
+    /// halving the time spent waiting on a lock.
+    #[must_use]
+    pub const fn scaled_sigma(self, factor: f64) -> Model {
+        self.with_sigma(self.sigma * factor)
+    }
+
+    /// Return a copy of the model with κ multiplied by the given factor.
+    #[must_use]
+    pub const fn scaled_kappa(self, factor: f64) -> Model {
+        self.with_kappa(self.kappa * factor)
+    }
+
+    /// Return a copy of the model with λ multiplied by the given factor.
+    #[must_use]
+    pub const fn scaled_lambda(self, factor: f64) -> Model {
+        self.with_lambda(self.lambda * factor)
+    }
+
+    /// Compare the capacity of this model with that of an adjusted copy of it, e.g. one produced
+    /// via [Model::scaled_sigma], to estimate the payoff of an optimization before making it.
+    ///
+    /// ```
+    /// use usl::Model;
+    ///
+    /// let model = Model { sigma: 0.02, kappa: 0.0001, lambda: 1000.0 };
+    /// let what_if = model.what_if(model.scaled_sigma(0.5));
+    /// println!("halving σ gains {:.0}% throughput", what_if.throughput_gain * 100.0);
+    /// ```
+    #[must_use]
+    pub fn what_if(&self, adjusted: Model) -> WhatIf {
+        let before = self.max_throughput();
+        let after = adjusted.max_throughput();
+        WhatIf {
+            max_throughput: (before, after),
+            max_concurrency: (self.checked_max_concurrency(), adjusted.checked_max_concurrency()),
+            throughput_gain: after / before - 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    #[test]
+    fn adjustments() {
+        let model = Model { sigma: 0.02, kappa: 0.0001, lambda: 1000.0 };
+        let adjusted = model.scaled_sigma(0.5).scaled_kappa(2.0).scaled_lambda(3.0);
+        assert_relative_eq!(adjusted.sigma, 0.01);
+        assert_relative_eq!(adjusted.kappa, 0.0002);
+        assert_relative_eq!(adjusted.lambda, 3000.0);
+
+        let adjusted = model.with_sigma(0.1).with_kappa(0.0).with_lambda(10.0);
+        assert_relative_eq!(adjusted.sigma, 0.1);
+        assert_relative_eq!(adjusted.kappa, 0.0);
+        assert_relative_eq!(adjusted.lambda, 10.0);
+    }
+
+    #[test]
+    fn what_if() {
+        let model: Model = MEASUREMENTS.iter().collect();
+
+        let what_if = model.what_if(model.scaled_sigma(0.5));
+        assert_eq!(what_if.max_concurrency, (Some(35), Some(35)));
+        assert!(what_if.max_throughput.1 > what_if.max_throughput.0);
+        assert_relative_eq!(
+            what_if.throughput_gain,
+            what_if.max_throughput.1 / what_if.max_throughput.0 - 1.0
+        );
+
+        let what_if = model.what_if(model.with_kappa(0.0));
+        assert_eq!(what_if.max_concurrency.1, None);
+    }
+}
@@ -0,0 +1,159 @@
+use std::time::Duration;
+
+use crate::{Measurement, Model, ModelFitter, TimedMeasurement, MIN_MEASUREMENTS};
+
+/// The extent of the windows used by [Model::build_windowed].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Window {
+    /// Each window contains the given number of most recent measurements.
+    Count(usize),
+    /// Each window contains the measurements taken within the given duration of its most recent
+    /// measurement, inclusive.
+    Duration(Duration),
+}
+
+/// A model fitted to the measurements in a window of time, via [Model::build_windowed].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowedModel {
+    /// The time of the earliest measurement in the window.
+    pub start: Duration,
+    /// The time of the latest measurement in the window.
+    pub end: Duration,
+    /// The number of measurements in the window.
+    pub len: usize,
+    /// The model fitted to the measurements in the window.
+    pub model: Model,
+}
+
+impl Model {
+    /// Fit a model to each sliding window of the given measurements, producing a time series of
+    /// models which shows how a system's σ, κ, and λ evolve.
+    ///
+    /// A window ends at each measurement, in order of time. Windows containing fewer than
+    /// [MIN_MEASUREMENTS] measurements (or, for [Window::Count], fewer than the given number of
+    /// measurements), or which can't be fitted, are skipped. Each window's fit
+    /// starts from the previous window's parameters.
+    #[must_use]
+    pub fn build_windowed(measurements: &[TimedMeasurement], window: Window) -> Vec<WindowedModel> {
+        let mut sorted = measurements.to_vec();
+        sorted.sort_by_key(|m| m.time);
+
+        let mut models: Vec<WindowedModel> = Vec::new();
+        let mut start = 0;
+        for end in 0..sorted.len() {
+            match window {
+                Window::Count(count) => start = (end + 1).saturating_sub(count),
+                Window::Duration(duration) => {
+                    while sorted[end].time - sorted[start].time > duration {
+                        start += 1;
+                    }
+                }
+            }
+
+            let len = end + 1 - start;
+            if len < MIN_MEASUREMENTS || matches!(window, Window::Count(count) if len < count) {
+                continue;
+            }
+            let window = &sorted[start..=end];
+            let window: Vec<Measurement> = window.iter().map(|m| m.measurement).collect();
+            let fitter =
+                ModelFitter { start: models.last().map(|m| m.model), ..ModelFitter::new(&window) };
+            if let Ok(model) = Model::try_fit(&fitter, None) {
+                models.push(WindowedModel {
+                    start: sorted[start].time,
+                    end: sorted[end].time,
+                    len: window.len(),
+                    model,
+                });
+            }
+        }
+        models
+    }
+}
+
+impl Model {
+    /// Build a model whose measurements are weighted by their recency, so it tracks the system's
+    /// current behavior while still remembering past behavior.
+    ///
+    /// Each measurement's weight decays exponentially with its age relative to the most recent
+    /// measurement, halving every `half_life`. See [Model::build_weighted].
+    #[must_use]
+    pub fn build_recency_weighted(measurements: &[TimedMeasurement], half_life: Duration) -> Model {
+        assert!(!half_life.is_zero(), "half-life must be non-zero");
+        let latest = measurements.iter().map(|m| m.time).max().unwrap_or_default();
+        let weighted: Vec<(Measurement, f64)> = measurements
+            .iter()
+            .map(|m| {
+                let age = (latest - m.time).as_secs_f64() / half_life.as_secs_f64();
+                (m.measurement, 0.5f64.powf(age))
+            })
+            .collect();
+        Model::build_weighted(&weighted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::MEASUREMENTS;
+
+    fn timed() -> Vec<TimedMeasurement> {
+        MEASUREMENTS
+            .iter()
+            .enumerate()
+            .map(|(i, &m)| TimedMeasurement::new(Duration::from_secs(i as u64 * 10), m.into()))
+            .collect()
+    }
+
+    #[test]
+    fn build_windowed_by_count() {
+        let models = Model::build_windowed(&timed(), Window::Count(10));
+
+        assert_eq!(models.len(), 32 - 10 + 1);
+        assert!(models.iter().all(|m| m.len == 10));
+        assert_eq!(models[0].start, Duration::ZERO);
+        assert_eq!(models[0].end, Duration::from_secs(90));
+
+        let expected = Model::build(
+            &MEASUREMENTS[22..].iter().map(|&m| m.into()).collect::<Vec<Measurement>>(),
+        );
+        assert_relative_eq!(models[22].model.kappa, expected.kappa, max_relative = 0.0001);
+    }
+
+    #[test]
+    fn build_windowed_by_duration() {
+        let models = Model::build_windowed(&timed(), Window::Duration(Duration::from_secs(60)));
+
+        assert_eq!(models.len(), 32 - MIN_MEASUREMENTS + 1);
+        assert_eq!(models[0].len, MIN_MEASUREMENTS);
+        assert!(models[1..].iter().all(|m| m.len == 7));
+        assert!(models[1..].iter().all(|m| m.end - m.start == Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn build_recency_weighted() {
+        let expected: Model = MEASUREMENTS.iter().collect();
+
+        // An hour of measurements from a system twice as fast, followed by the current system.
+        let mut measurements: Vec<TimedMeasurement> = MEASUREMENTS
+            .iter()
+            .map(|&(n, x)| Measurement::concurrency_and_throughput(n, x * 2.0))
+            .map(|m| TimedMeasurement::new(Duration::ZERO, m))
+            .collect();
+        measurements.extend(
+            MEASUREMENTS
+                .iter()
+                .map(|&m| TimedMeasurement::new(Duration::from_secs(3600), m.into())),
+        );
+
+        let model = Model::build_recency_weighted(&measurements, Duration::from_secs(60));
+        assert_relative_eq!(model.lambda, expected.lambda, max_relative = 0.0001);
+
+        let model = Model::build_recency_weighted(&measurements, Duration::from_secs(3600));
+        assert!(model.lambda > expected.lambda * 1.1);
+    }
+}